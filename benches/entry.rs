@@ -5,8 +5,8 @@ mod perm;
 
 use group::{
     bruteforce_elements::bruteforce,
+    factorization::factorization,
     orbit::{
-        self,
         factored_transversal::factored_transversal_impl,
         orbit_impl,
         transversal::transversal_impl,
@@ -21,16 +21,19 @@ use group::stabilizer_chain::{
 use perm::{
     comparison::cmp,
     permutation,
+    simd::simd,
 };
 
 criterion_main!(
     permutation,
     cmp,
-    orbit,
+    simd,
+    group::orbit,
     orbit_impl,
     bruteforce,
     transversal_impl,
     factored_transversal_impl,
     stabchain,
-    selector
+    selector,
+    factorization
 );
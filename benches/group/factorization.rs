@@ -0,0 +1,37 @@
+use {
+    criterion::{
+        criterion_group,
+        Criterion,
+    },
+    stabchain::group::{
+        minkwitz::MinkwitzTable,
+        Group,
+    },
+};
+
+fn rubiks_cube_short_factorization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("group__factorization__rubiks_cube");
+    let g = Group::rubiks_cube();
+    group.bench_function("minkwitz_table", |b| b.iter(|| MinkwitzTable::new(&g)));
+
+    let table = MinkwitzTable::new(&g);
+    let chain = g.stabchain_with_words();
+    let mut rng = g.rng();
+    group.bench_function("short_factorization", |b| {
+        b.iter_batched(
+            || rng.random_permutation(),
+            |p| table.factorize(&p),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("sift_factorization", |b| {
+        b.iter_batched(
+            || rng.random_permutation(),
+            |p| chain.factorize(&p),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(factorization, rubiks_cube_short_factorization);
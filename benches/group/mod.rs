@@ -1,4 +1,5 @@
 pub mod bruteforce_elements;
+pub mod factorization;
 pub mod orbit;
 pub mod stabilizer_chain;
 
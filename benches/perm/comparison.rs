@@ -12,6 +12,8 @@ use stabchain::perm::Permutation;
 use stabchain::perm::impls::{
     based::BasedPermutation,
     map::MapPermutation,
+    small::SmallPermutation,
+    sparse::SparsePermutation,
     standard::StandardPermutation,
     sync::SyncPermutation,
     word::WordPermutation,
@@ -22,9 +24,7 @@ const RANGE_OF_VALUES: [usize; 7] = [8, 16, 32, 64, 128, 256, 512];
 macro_rules! cmp_permutation {
     ($($types:ty, )*) => {
         fn random_instantiation(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__random_creation"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__random_creation");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -37,9 +37,7 @@ macro_rules! cmp_permutation {
         }
 
         fn simple_application(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__simple_application"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__simple_application");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -53,9 +51,7 @@ macro_rules! cmp_permutation {
         }
 
         fn inverses(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__inverses"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__inverses");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -69,9 +65,7 @@ macro_rules! cmp_permutation {
         }
 
         fn lmp(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__lmp"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__lmp");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -85,9 +79,7 @@ macro_rules! cmp_permutation {
         }
 
         fn lmp_multiple(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__lmp_multiple"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__lmp_multiple");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -101,9 +93,7 @@ macro_rules! cmp_permutation {
         }
 
         fn single_multiplication(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__single_mult"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__single_mult");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -118,9 +108,7 @@ macro_rules! cmp_permutation {
         }
 
         fn multiple_multiplication(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__multiple_mult"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__multiple_mult");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -134,9 +122,7 @@ macro_rules! cmp_permutation {
         }
 
         fn multiple_multiplication_and_application(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__multiple_mult_and_appl"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__multiple_mult_and_appl");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -152,9 +138,7 @@ macro_rules! cmp_permutation {
         }
 
         fn order(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__order"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__order");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -168,9 +152,7 @@ macro_rules! cmp_permutation {
         }
 
         fn shift(c: &mut Criterion) {
-            let mut group = c.benchmark_group(concat!(
-                "permutation__cmp__shift"
-            ));
+            let mut group = c.benchmark_group("permutation__cmp__shift");
             for i in RANGE_OF_VALUES.iter() {
                 $(
                 group.bench_with_input(BenchmarkId::new(stringify!($types), i), i, |b, i| {
@@ -192,6 +174,9 @@ cmp_permutation!(
     BasedPermutation,
     MapPermutation,
     WordPermutation,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation,
 );
 
 criterion_group!(
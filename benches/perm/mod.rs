@@ -1,4 +1,5 @@
 pub mod comparison;
+pub mod simd;
 
 use criterion::{
     black_box,
@@ -0,0 +1,93 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    BenchmarkId,
+    Criterion,
+};
+
+use rand::seq::SliceRandom;
+
+const RANGE_OF_VALUES: [usize; 7] = [8, 16, 32, 64, 128, 256, 512];
+
+fn random_images(n: usize) -> Vec<usize> {
+    let mut images: Vec<_> = (0..n).collect();
+    images.shuffle(&mut rand::thread_rng());
+    images
+}
+
+#[cfg(feature = "simd")]
+fn narrow<T: std::convert::TryFrom<usize>>(images: &[usize]) -> Vec<T> {
+    images.iter().map(|&i| T::try_from(i).ok().unwrap()).collect()
+}
+
+/// The scalar loop of the multiplication against the vectorized gather, which is only run with the simd feature
+fn gather(c: &mut Criterion) {
+    let mut group = c.benchmark_group("permutation__simd__gather");
+    for i in RANGE_OF_VALUES.iter() {
+        let table = random_images(*i);
+        let indices = random_images(*i);
+        group.bench_with_input(BenchmarkId::new("scalar", i), i, |b, _| {
+            b.iter(|| black_box(indices.iter().map(|&x| table[x]).collect::<Vec<_>>()))
+        });
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("simd", i), i, |b, _| {
+            b.iter(|| {
+                let mut out = Vec::with_capacity(indices.len());
+                unsafe { stabchain::perm::simd::gather(&table, &indices, &mut out) };
+                black_box(out)
+            })
+        });
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("simd_u32", i), i, |b, _| {
+            let (table, indices) = (narrow::<u32>(&table), narrow::<u32>(&indices));
+            b.iter(|| {
+                let mut out = Vec::with_capacity(indices.len());
+                unsafe { stabchain::perm::simd::gather_u32(&table, &indices, &mut out) };
+                black_box(out)
+            })
+        });
+        #[cfg(feature = "simd")]
+        if *i <= 64 {
+            group.bench_with_input(BenchmarkId::new("simd_u8", i), i, |b, _| {
+                let (table, indices) = (narrow::<u8>(&table), narrow::<u8>(&indices));
+                b.iter(|| {
+                    let mut out = Vec::with_capacity(indices.len());
+                    unsafe { stabchain::perm::simd::gather_u8(&table, &indices, &mut out) };
+                    black_box(out)
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// The scalar loop of the inversion against the vectorized scatter, which is only run with the simd feature
+fn invert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("permutation__simd__invert");
+    for i in RANGE_OF_VALUES.iter() {
+        let images = random_images(*i);
+        group.bench_with_input(BenchmarkId::new("scalar", i), i, |b, _| {
+            b.iter(|| {
+                let mut inverse = vec![0; images.len()];
+                for (x, &image) in images.iter().enumerate() {
+                    inverse[image] = x;
+                }
+                black_box(inverse)
+            })
+        });
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("simd", i), i, |b, _| {
+            b.iter(|| black_box(unsafe { stabchain::perm::simd::invert(&images) }))
+        });
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("simd_u32", i), i, |b, _| {
+            let images = narrow::<u32>(&images);
+            b.iter(|| black_box(unsafe { stabchain::perm::simd::invert_u32(&images) }))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(simd, gather, invert);
@@ -23,6 +23,7 @@ use stabchain::{
         Group,
     },
     perm::export::{
+        binary::BinaryWriter,
         ClassicalPermutation,
         ExportablePermutation,
     },
@@ -35,7 +36,7 @@ struct GAPGroup {
 }
 
 impl GAPGroup {
-    fn to_group(self) -> Group {
+    fn into_group(self) -> Group {
         self.generators
             .into_iter()
             .map(|images| ClassicalPermutation::from_slice(&images[..]).into())
@@ -50,6 +51,10 @@ struct Arguments {
 
     #[structopt(short, long, parse(from_os_str))]
     output: PathBuf,
+
+    /// Write the groups in the compact binary format instead of JSON
+    #[structopt(short, long)]
+    binary: bool,
 }
 
 fn main() -> std::io::Result<()> {
@@ -78,15 +83,21 @@ fn main() -> std::io::Result<()> {
         .into_iter()
         .map(|g| {
             let size = g.size.clone();
-            DecoratedGroup::new(
-                g.to_group().map(ExportablePermutation::from),
-                size.to_string().parse::<BigUint>().unwrap(),
-            )
+            DecoratedGroup::new(g.into_group(), size.to_string().parse::<BigUint>().unwrap())
         })
         .collect();
 
     let out = BufWriter::new(File::create(&args.output)?);
-    serde_json::to_writer(out, &groups)?;
+    if args.binary {
+        let mut writer = BinaryWriter::new(out)?;
+        for g in &groups {
+            writer.write_decorated_group(g)?;
+        }
+        writer.flush()?;
+    } else {
+        let groups: Vec<_> = groups.into_iter().map(|g| g.map(ExportablePermutation::from)).collect();
+        serde_json::to_writer(out, &groups)?;
+    }
 
     Ok(())
 }
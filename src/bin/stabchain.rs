@@ -0,0 +1,421 @@
+//! Command line interface to the library, so that group computations can be run from shell scripts.
+//!
+//! Groups are read from a file (or from standard input, given as `-`) in GAP syntax, in JSON, or in the
+//! notations of Magma, Sage and Mathematica. Permutations are always printed in GAP syntax.
+
+use std::{
+    error::Error,
+    fs,
+    io::{
+        self,
+        Read,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process,
+    str::FromStr,
+};
+
+use {
+    rand::Rng,
+    serde::Deserialize,
+    structopt::StructOpt,
+};
+
+use stabchain::{
+    group::{
+        group_library::DecoratedGroup,
+        orbit::{
+            abstraction::TransversalResolver,
+            transversal::Transversal,
+        },
+        stabchain::{
+            base::selectors::LmpSelector,
+            builder::*,
+            Stabchain,
+        },
+        Group,
+    },
+    perm::{
+        actions::SimpleApplication,
+        export::{
+            gap,
+            magma,
+            mathematica,
+            sage,
+            CyclePermutation,
+            ExportablePermutation,
+        },
+        DefaultPermutation,
+        Permutation,
+    },
+};
+
+/// The algorithm used to build stabilizer chains, named as in the benchmarks
+#[derive(Debug, Clone, Copy)]
+enum Strategy {
+    Deterministic,
+    DeterministicIFT,
+    Random,
+    RandomShallow,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "deterministic" => Self::Deterministic,
+            "ift" => Self::DeterministicIFT,
+            "random" => Self::Random,
+            "shallow" => Self::RandomShallow,
+            _ => return Err(format!("unknown strategy `{}`", s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Gap,
+    Json,
+    Magma,
+    Sage,
+    Mathematica,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "gap" => Self::Gap,
+            "json" => Self::Json,
+            "magma" => Self::Magma,
+            "sage" => Self::Sage,
+            "mathematica" => Self::Mathematica,
+            _ => return Err(format!("unknown format `{}`", s)),
+        })
+    }
+}
+
+impl Format {
+    /// Guess the format of a file from its extension, defaulting to GAP
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::Json,
+            Some("magma") => Self::Magma,
+            Some("sage") => Self::Sage,
+            Some("m") | Some("wl") => Self::Mathematica,
+            _ => Self::Gap,
+        }
+    }
+}
+
+#[derive(StructOpt)]
+#[structopt(name = "stabchain", about = "Computations in permutation groups")]
+struct Arguments {
+    /// How stabilizer chains are built: deterministic, ift, random or shallow
+    #[structopt(short, long, global = true, default_value = "deterministic")]
+    strategy: Strategy,
+
+    /// Format of the input: gap, json, magma, sage or mathematica. Guessed from the extension if not given
+    #[structopt(short, long, global = true)]
+    format: Option<Format>,
+
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Print the order of the group
+    Order {
+        /// File containing the group, or `-` for standard input
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+    },
+    /// Check whether a permutation, in GAP syntax, is in the group. Exits with status 1 if it is not
+    Contains {
+        /// File containing the group, or `-` for standard input
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+        /// The permutation, e.g. `(1,2,3)(4,5)`
+        perm: String,
+    },
+    /// Print the base of a stabilizer chain of the group
+    Base {
+        /// File containing the group, or `-` for standard input
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+    },
+    /// Print the orbits of the group on the points it acts on, one per line
+    Orbits {
+        /// File containing the group, or `-` for standard input
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+    },
+    /// Print uniformly random elements of the group, one per line
+    Random {
+        /// File containing the group, or `-` for standard input
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+        /// How many elements to print
+        #[structopt(short = "n", long, default_value = "1")]
+        count: usize,
+    },
+    /// Write the group in another format
+    Convert {
+        /// File containing the group, or `-` for standard input
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+        /// The output format: gap, json, magma, sage or mathematica
+        #[structopt(short, long, default_value = "gap")]
+        to: Format,
+    },
+}
+
+impl Command {
+    fn input(&self) -> &Path {
+        match self {
+            Self::Order { input }
+            | Self::Contains { input, .. }
+            | Self::Base { input }
+            | Self::Orbits { input }
+            | Self::Random { input, .. }
+            | Self::Convert { input, .. } => input,
+        }
+    }
+}
+
+/// The shapes of JSON accepted: a serialized `Group`, a `DecoratedGroup` or a bare list of generators,
+/// with the permutations given as lists of images on 1..=n
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonGroup {
+    Group(Group<ExportablePermutation>),
+    Decorated(DecoratedGroup<ExportablePermutation>),
+    Generators(Vec<ExportablePermutation>),
+}
+
+impl JsonGroup {
+    fn into_group(self) -> Result<Group, stabchain::Error> {
+        let generators = match self {
+            Self::Group(g) => g.generators().to_vec(),
+            Self::Decorated(g) => g.group().generators().to_vec(),
+            Self::Generators(gens) => gens,
+        };
+        generators
+            .into_iter()
+            .map(ExportablePermutation::try_into_perm)
+            .collect()
+    }
+}
+
+fn parse_group(text: &str, format: Format) -> Result<Group, Box<dyn Error>> {
+    Ok(match format {
+        Format::Gap => gap::parse_group(text)?,
+        Format::Json => serde_json::from_str::<JsonGroup>(text)?.into_group()?,
+        Format::Magma => magma::parse_group(text)?,
+        Format::Sage => sage::parse_group(text)?,
+        Format::Mathematica => mathematica::parse_group(text)?,
+    })
+}
+
+fn group_to_string(g: &Group, format: Format) -> Result<String, Box<dyn Error>> {
+    Ok(match format {
+        Format::Gap => gap::group_to_string(g),
+        Format::Json => serde_json::to_string(&g.clone().map(ExportablePermutation::from))?,
+        Format::Magma => magma::group_to_string(g),
+        Format::Sage => sage::group_to_string(g),
+        Format::Mathematica => mathematica::group_to_string(g),
+    })
+}
+
+fn read_group(path: &Path, format: Option<Format>) -> Result<Group, Box<dyn Error>> {
+    let mut text = String::new();
+    if path == Path::new("-") {
+        io::stdin().read_to_string(&mut text)?;
+    } else {
+        text = fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    }
+
+    parse_group(&text, format.unwrap_or_else(|| Format::from_path(path)))
+}
+
+fn perm_to_string(perm: &DefaultPermutation) -> String {
+    gap::permutation_to_string(&CyclePermutation::from(perm.clone()))
+}
+
+fn list_to_string<'a>(points: impl IntoIterator<Item = &'a usize>) -> String {
+    let points: Vec<_> = points.into_iter().map(|x| (x + 1).to_string()).collect();
+    format!("[{}]", points.join(", "))
+}
+
+/// The orbits of the group on 0..n, where n is the largest moved point, in order of their smallest point
+fn orbits(g: &Group) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; g.symmetric_super_order()];
+    let mut orbits = Vec::new();
+    for point in 0..seen.len() {
+        if seen[point] {
+            continue;
+        }
+
+        let mut orbit: Vec<_> = g.orbit(point).iter().cloned().collect();
+        orbit.sort_unstable();
+        for &x in &orbit {
+            seen[x] = true;
+        }
+        orbits.push(orbit);
+    }
+    orbits
+}
+
+/// A uniformly random element, as a product of random coset representatives of each layer
+fn random_element<V, R>(chain: &Stabchain<DefaultPermutation, V>, rng: &mut R) -> DefaultPermutation
+where
+    V: TransversalResolver<DefaultPermutation>,
+    R: Rng,
+{
+    chain.iter().fold(DefaultPermutation::id(), |acc, record| {
+        let transversal = record.transversal();
+        let orbit = transversal.orbit();
+        let point = *orbit.iter().nth(rng.gen_range(0..orbit.len())).unwrap();
+        transversal.representative(point).unwrap().multiply(&acc)
+    })
+}
+
+/// Run the commands that need a stabilizer chain, returning whether the process should succeed
+fn with_chain<S>(g: &Group, command: &Command, strategy: S) -> Result<bool, Box<dyn Error>>
+where
+    S: BuilderStrategy<DefaultPermutation, Action = SimpleApplication<DefaultPermutation>>,
+{
+    let chain = g.stabchain_with_strategy(strategy);
+    match command {
+        Command::Order { .. } => println!("{}", chain.order()),
+        Command::Contains { perm, .. } => {
+            let perm: DefaultPermutation = gap::parse_permutation(perm)?.into_perm();
+            let contained = chain.in_group(&perm);
+            println!("{}", contained);
+            return Ok(contained);
+        }
+        Command::Base { .. } => println!("{}", list_to_string(chain.base().iter())),
+        Command::Random { count, .. } => {
+            let mut rng = rand::thread_rng();
+            for _ in 0..*count {
+                println!("{}", perm_to_string(&random_element(&chain, &mut rng)));
+            }
+        }
+        Command::Orbits { .. } | Command::Convert { .. } => unreachable!(),
+    }
+    Ok(true)
+}
+
+fn run(args: Arguments) -> Result<bool, Box<dyn Error>> {
+    let g = read_group(args.command.input(), args.format)?;
+
+    match &args.command {
+        Command::Orbits { .. } => {
+            for orbit in orbits(&g) {
+                println!("{}", list_to_string(&orbit));
+            }
+            return Ok(true);
+        }
+        Command::Convert { to, .. } => {
+            println!("{}", group_to_string(&g, *to)?);
+            return Ok(true);
+        }
+        _ => {}
+    }
+
+    #[allow(deprecated)]
+    match args.strategy {
+        Strategy::Deterministic => with_chain(
+            &g,
+            &args.command,
+            DefaultStrategy::new(SimpleApplication::default(), LmpSelector),
+        ),
+        Strategy::DeterministicIFT => with_chain(
+            &g,
+            &args.command,
+            IftBuilderStrategy::new(SimpleApplication::default(), LmpSelector),
+        ),
+        Strategy::Random => with_chain(
+            &g,
+            &args.command,
+            RandomBuilderStrategyNaive::new(SimpleApplication::default(), LmpSelector),
+        ),
+        Strategy::RandomShallow => with_chain(
+            &g,
+            &args.command,
+            RandomBuilderStrategyShallow::new(SimpleApplication::default(), LmpSelector),
+        ),
+    }
+}
+
+fn main() {
+    let args = Arguments::from_args();
+    match run(args) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("stabchain: {}", e);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_inputs() {
+        let expected = "Group([(1,2,3),(1,2)])";
+        for text in &[
+            "{\"generators\": [[2, 3, 1], [2, 1]]}",
+            "[[2, 3, 1], [2, 1]]",
+            "{\"group\": {\"generators\": [[2, 3, 1], [2, 1]]}, \"size\": [6]}",
+        ] {
+            let g = parse_group(text, Format::Json).unwrap();
+            assert_eq!(gap::group_to_string(&g), expected);
+        }
+        assert!(parse_group("{\"gens\": []}", Format::Json).is_err());
+        assert!(parse_group("[[0, 1]]", Format::Json).is_err());
+        assert!(parse_group("[[1, 1]]", Format::Json).is_err());
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        let g = Group::rubiks_cube();
+        for &format in &[
+            Format::Gap,
+            Format::Json,
+            Format::Magma,
+            Format::Sage,
+            Format::Mathematica,
+        ] {
+            let parsed = parse_group(&group_to_string(&g, format).unwrap(), format).unwrap();
+            assert_eq!(parsed.generators(), g.generators());
+        }
+        assert_eq!(Format::from_path(Path::new("groups/s5.json")), Format::Json);
+        assert_eq!(Format::from_path(Path::new("s5.g")), Format::Gap);
+    }
+
+    #[test]
+    fn orbits_of_product() {
+        let g = Group::product(&Group::symmetric(3), &Group::cyclic(2));
+        assert_eq!(orbits(&g), vec![vec![0, 1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn random_elements_in_group() {
+        let g = Group::dihedral_2n(7);
+        let chain = g.stabchain();
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            assert!(chain.in_group(&random_element(&chain, &mut rng)));
+        }
+    }
+}
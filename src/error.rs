@@ -0,0 +1,74 @@
+//! Errors returned when the data given to the library is not valid
+
+use {
+    crate::{
+        group::stabchain::builder::LimitError,
+        perm::{
+            export::ParseError,
+            utils::ImageError,
+        },
+    },
+    std::fmt,
+};
+
+/// The error returned by the fallible constructors of the crate, so that invalid user data does not cause a panic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The images do not define a permutation
+    InvalidImages(ImageError),
+    /// A point is 0, in a notation where points are numbered from 1
+    ZeroPoint,
+    /// A point does not fit in the integer type used to store the images
+    PointTooLarge(usize),
+    /// A point appears more than once in a list of disjoint cycles
+    RepeatedPoint(usize),
+    /// A fixed base of the given length is not a base of the group
+    BaseTooShort(usize),
+    /// The text could not be parsed
+    Parse(ParseError),
+    /// The construction of a stabilizer chain exceeded its limits
+    Limit(LimitError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidImages(e) => write!(f, "invalid images: {}", e),
+            Self::ZeroPoint => write!(f, "points are numbered from 1"),
+            Self::PointTooLarge(x) => write!(f, "the point {} is too large for the image type", x),
+            Self::RepeatedPoint(x) => write!(f, "the point {} appears more than once", x),
+            Self::BaseTooShort(len) => write!(f, "the base of length {} is shorter than needed", len),
+            Self::Parse(e) => e.fmt(f),
+            Self::Limit(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidImages(e) => Some(e),
+            Self::Parse(e) => Some(e),
+            Self::Limit(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ImageError> for Error {
+    fn from(e: ImageError) -> Self {
+        Self::InvalidImages(e)
+    }
+}
+
+impl From<LimitError> for Error {
+    fn from(e: LimitError) -> Self {
+        Self::Limit(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
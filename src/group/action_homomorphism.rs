@@ -0,0 +1,190 @@
+//! Turn the action of a group on a finite orbit into a permutation group.
+//!
+//! Any action on a finite orbit induces a permutation of the points of the orbit, so after numbering the points
+//! we get a permutation group on 0..n, which can then be used with all the usual algorithms
+//! (i.e. computing a stabilizer chain of the action of a group on itself by conjugation).
+
+use {
+    super::{
+        homomorphism::GroupHomomorphism,
+        orbit::Orbit,
+        Group,
+    },
+    crate::{
+        perm::{
+            impls::standard::StandardPermutation,
+            Action,
+            Permutation,
+        },
+        DetHashMap,
+    },
+};
+
+/// The homomorphism from a group to the permutations of a finite orbit of one of its actions.
+/// Keeps track of the bijection between the points of the orbit and the integers 0..n.
+#[derive(Debug, Clone)]
+pub struct ActionHomomorphism<P, A>
+where
+    A: Action<P>,
+{
+    source: Group<P>,
+    action: A,
+    points: Vec<A::OrbitT>,
+    indices: DetHashMap<A::OrbitT, usize>,
+    image: Group<StandardPermutation>,
+}
+
+impl<P, A> ActionHomomorphism<P, A>
+where
+    P: Permutation,
+    A: Action<P>,
+{
+    /// Compute the action of the group on the orbit of w. Note that the orbit needs to be finite.
+    /// The base point w is always mapped to 0.
+    pub fn new(g: &Group<P>, w: A::OrbitT, action: A) -> Self {
+        let orbit = Orbit::new_with_action(g, w, &action);
+        Self::from_orbit(g, &orbit, action)
+    }
+
+    /// Compute the action of the group on an already computed orbit
+    pub fn from_orbit(g: &Group<P>, orbit: &Orbit<A::OrbitT>, action: A) -> Self {
+        let points: Vec<_> = std::iter::once(orbit.base().clone())
+            .chain(orbit.iter().filter(|&x| x != orbit.base()).cloned())
+            .collect();
+        let indices = points.iter().cloned().enumerate().map(|(i, x)| (x, i)).collect();
+
+        let mut homomorphism = Self {
+            source: g.clone(),
+            action,
+            points,
+            indices,
+            image: Group::new(&[]),
+        };
+
+        homomorphism.image = g
+            .generators()
+            .iter()
+            .map(|p| {
+                homomorphism
+                    .image(p)
+                    .expect("The orbit should be closed under the group")
+            })
+            .collect();
+
+        homomorphism
+    }
+
+    /// The group acting
+    pub fn source(&self) -> &Group<P> {
+        &self.source
+    }
+
+    /// The action the homomorphism is induced by
+    pub fn action(&self) -> &A {
+        &self.action
+    }
+
+    /// The induced permutation group on 0..n
+    pub fn image_group(&self) -> &Group<StandardPermutation> {
+        &self.image
+    }
+
+    /// Compute the permutation induced by p, or None if p does not preserve the orbit
+    pub fn image(&self, p: &P) -> Option<StandardPermutation> {
+        let images = self
+            .points
+            .iter()
+            .map(|x| self.index_of(&self.action.apply(p, x.clone())))
+            .collect::<Option<Vec<_>>>()?;
+        Some(StandardPermutation::from_images(&images[..]))
+    }
+
+    /// The points of the orbit, so that the i-th point is the one mapped to i
+    pub fn points(&self) -> &[A::OrbitT] {
+        &self.points[..]
+    }
+
+    /// Get the point mapped to i
+    pub fn point(&self, i: usize) -> Option<&A::OrbitT> {
+        self.points.get(i)
+    }
+
+    /// Get the integer a point of the orbit is mapped to
+    pub fn index_of(&self, x: &A::OrbitT) -> Option<usize> {
+        self.indices.get(x).copied()
+    }
+
+    /// Number of points in the orbit
+    pub fn degree(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Get the homomorphism into the induced permutation group, to compute kernels and preimages
+    pub fn homomorphism(&self) -> GroupHomomorphism<P, StandardPermutation> {
+        let images: Vec<_> = self
+            .source
+            .generators()
+            .iter()
+            .map(|p| self.image(p).unwrap())
+            .collect();
+        GroupHomomorphism::new(&self.source, &images[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::{
+            actions::{
+                ConjugationAction,
+                MultiplicationAction,
+            },
+            export::CyclePermutation,
+            DefaultPermutation,
+        },
+    };
+
+    #[test]
+    fn regular_action() {
+        let g = Group::symmetric(4);
+        let hom = ActionHomomorphism::new(&g, DefaultPermutation::id(), MultiplicationAction::default());
+        assert_eq!(hom.degree(), 24);
+        assert_eq!(hom.index_of(&DefaultPermutation::id()), Some(0));
+        assert_eq!(hom.image_group().stabchain().order(), 24u8.into());
+
+        // The regular action is faithful
+        assert!(hom.homomorphism().kernel().generators().is_empty());
+    }
+
+    #[test]
+    fn conjugation_action() {
+        // S_4 acts on its 3 double transpositions by conjugation, with kernel the Klein 4 group
+        let g = Group::symmetric(4);
+        let p: DefaultPermutation = CyclePermutation::from_vec(vec![vec![1, 2], vec![3, 4]]).into();
+        let hom = ActionHomomorphism::new(&g, p.clone(), ConjugationAction::default());
+        assert_eq!(hom.degree(), 3);
+        assert_eq!(hom.point(0), Some(&p));
+        assert_eq!(hom.image_group().stabchain().order(), 6u8.into());
+        assert_eq!(hom.homomorphism().kernel().stabchain().order(), 4u8.into());
+    }
+
+    #[test]
+    fn images_agree_with_action() {
+        let g = Group::alternating(5);
+        let hom = ActionHomomorphism::new(&g, DefaultPermutation::id(), MultiplicationAction::default());
+        let action = MultiplicationAction::default();
+        let mut rng = g.rng();
+        for _ in 0..10 {
+            let p = rng.random_permutation();
+            let image = hom.image(&p).unwrap();
+            for (i, x) in hom.points().iter().enumerate() {
+                assert_eq!(hom.point(image.apply(i)), Some(&action.apply(&p, x.clone())));
+            }
+        }
+
+        // An odd permutation does not preserve the orbit of the identity
+        let transposition: DefaultPermutation = CyclePermutation::single_cycle(&[1, 2]).into();
+        assert_eq!(hom.image(&transposition), None);
+    }
+}
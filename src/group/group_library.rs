@@ -9,9 +9,11 @@ use {
 use {
     super::Group,
     crate::perm::{
+        export::CyclePermutation,
         DefaultPermutation,
         Permutation,
     },
+    num::integer::gcd,
 };
 
 /// A simple struct that keeps track of additional information of a group, such
@@ -50,6 +52,289 @@ impl<P> DecoratedGroup<P> {
     }
 }
 
+impl DecoratedGroup {
+    /// The Mathieu group M_n, for n in 11, 12, 22, 23 and 24, in its action on n points
+    pub fn mathieu(n: usize) -> Self {
+        let (generators, order): (&[&[&[usize]]], u64) = match n {
+            11 => (
+                &[
+                    &[&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]],
+                    &[&[3, 7, 11, 8], &[4, 10, 5, 6]],
+                ],
+                7920,
+            ),
+            12 => (
+                &[
+                    &[&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]],
+                    &[&[3, 7, 11, 8], &[4, 10, 5, 6]],
+                    &[&[1, 12], &[2, 11], &[3, 6], &[4, 8], &[5, 9], &[7, 10]],
+                ],
+                95040,
+            ),
+            22 => (
+                &[
+                    &[
+                        &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+                        &[12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22],
+                    ],
+                    &[
+                        &[1, 4, 5, 9, 3],
+                        &[2, 8, 10, 7, 6],
+                        &[12, 15, 16, 20, 14],
+                        &[13, 19, 21, 18, 17],
+                    ],
+                    &[
+                        &[1, 21],
+                        &[2, 10, 8, 6],
+                        &[3, 13, 4, 17],
+                        &[5, 19, 9, 18],
+                        &[11, 22],
+                        &[12, 14, 16, 20],
+                    ],
+                ],
+                443520,
+            ),
+            23 => (
+                &[
+                    &[&[
+                        1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+                    ]],
+                    &[
+                        &[3, 17, 10, 7, 9],
+                        &[4, 13, 14, 19, 5],
+                        &[8, 18, 11, 12, 23],
+                        &[15, 20, 22, 21, 16],
+                    ],
+                ],
+                10200960,
+            ),
+            24 => (
+                &[
+                    &[&[
+                        1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+                    ]],
+                    &[
+                        &[3, 17, 10, 7, 9],
+                        &[4, 13, 14, 19, 5],
+                        &[8, 18, 11, 12, 23],
+                        &[15, 20, 22, 21, 16],
+                    ],
+                    &[
+                        &[1, 24],
+                        &[2, 23],
+                        &[3, 12],
+                        &[4, 16],
+                        &[5, 18],
+                        &[6, 10],
+                        &[7, 20],
+                        &[8, 14],
+                        &[9, 21],
+                        &[11, 17],
+                        &[13, 22],
+                        &[15, 19],
+                    ],
+                ],
+                244823040,
+            ),
+            _ => panic!("There is no Mathieu group M_{}", n),
+        };
+
+        let group = generators
+            .iter()
+            .map(|cycles| CyclePermutation::from_vec(cycles.iter().map(|c| c.to_vec()).collect()).into_perm())
+            .collect();
+        Self::new(group, order.into())
+    }
+
+    /// The projective special linear group PSL(2, q), acting on the q + 1 points of the projective line over GF(q).
+    /// q has to be a prime power.
+    pub fn psl_2(q: usize) -> Self {
+        let field = FiniteField::new(q);
+        let square = field.mul(field.primitive, field.primitive);
+        let group = Group::new(&[
+            field.mobius(|x| field.add(x, 1)),
+            field.mobius(|x| field.mul(square, x)),
+            field.inversion(),
+        ]);
+        let order = BigUint::from(q) * (q * q - 1) / gcd(2, q - 1);
+        Self::new(group, order)
+    }
+
+    /// The projective general linear group PGL(2, q), acting on the q + 1 points of the projective line over GF(q).
+    /// q has to be a prime power.
+    pub fn pgl_2(q: usize) -> Self {
+        let field = FiniteField::new(q);
+        let group = Group::new(&[
+            field.mobius(|x| field.add(x, 1)),
+            field.mobius(|x| field.mul(field.primitive, x)),
+            field.inversion(),
+        ]);
+        Self::new(group, BigUint::from(q) * (q * q - 1))
+    }
+
+    /// The affine group AGL(1, q) of the maps x -> ax + b over GF(q), acting on the q points of the field.
+    /// q has to be a prime power.
+    pub fn agl_1(q: usize) -> Self {
+        let field = FiniteField::new(q);
+        let affine = |f: &dyn Fn(usize) -> usize| DefaultPermutation::from_images(&(0..q).map(f).collect::<Vec<_>>());
+        let group = Group::new(&[affine(&|x| field.add(x, 1)), affine(&|x| field.mul(field.primitive, x))]);
+        Self::new(group, BigUint::from(q) * (q - 1))
+    }
+
+    /// The dicyclic group of order 4n, <a, x | a^2n = 1, x^2 = a^n, x^-1 a x = a^-1>, in its regular action
+    pub fn dicyclic(n: usize) -> Self {
+        assert!(n > 0);
+
+        // The element a^i x^j is the point 2nj + i, and the generators act by multiplication on the right
+        let point = |i: usize, j: usize| 2 * n * j + i % (2 * n);
+        let a: Vec<_> = (0..2 * n)
+            .map(|i| point(i + 1, 0))
+            .chain((0..2 * n).map(|i| point(i + 2 * n - 1, 1)))
+            .collect();
+        let x: Vec<_> = (0..2 * n)
+            .map(|i| point(i, 1))
+            .chain((0..2 * n).map(|i| point(i + n, 0)))
+            .collect();
+
+        let group = Group::new(&[DefaultPermutation::from_images(&a), DefaultPermutation::from_images(&x)]);
+        Self::new(group, BigUint::from(4 * n))
+    }
+
+    /// The generalized quaternion group of order 2^n, for n at least 3, in its regular action.
+    /// It is the dicyclic group of order 2^n.
+    pub fn generalized_quaternion(n: usize) -> Self {
+        assert!(n >= 3);
+
+        Self::dicyclic(1 << (n - 2))
+    }
+
+    /// The Rubik's cube group, see `Group::rubiks_cube`
+    pub fn rubiks_cube() -> Self {
+        Self::new(Group::rubiks_cube(), 43252003274489856000u128.into())
+    }
+}
+
+// The finite field with q = p^k elements. Its elements are the integers below q, whose digits in base p are the
+// coefficients of polynomials over GF(p), reduced modulo an irreducible polynomial of degree k
+struct FiniteField {
+    p: usize,
+    k: usize,
+    q: usize,
+    // The coefficients of the monic modulus, except for the leading one
+    modulus: Vec<usize>,
+    // A generator of the multiplicative group
+    primitive: usize,
+}
+
+impl FiniteField {
+    fn new(q: usize) -> Self {
+        assert!(q >= 2, "q has to be a prime power");
+        let p = (2..=q).find(|&p| q.is_multiple_of(p)).unwrap();
+        let mut k = 0;
+        let mut rest = q;
+        while rest.is_multiple_of(p) {
+            rest /= p;
+            k += 1;
+        }
+        assert_eq!(rest, 1, "q has to be a prime power");
+
+        // A modulus is irreducible exactly when the quotient has an element of multiplicative order q - 1
+        for modulus in 0..q {
+            let mut field = Self {
+                p,
+                k,
+                q,
+                modulus: field_digits(modulus, p, k),
+                primitive: 0,
+            };
+            if let Some(primitive) = (1..q).find(|&g| field.multiplicative_order(g) == Some(q - 1)) {
+                field.primitive = primitive;
+                return field;
+            }
+        }
+
+        unreachable!("There is an irreducible polynomial of every degree")
+    }
+
+    fn add(&self, a: usize, b: usize) -> usize {
+        let (a, b) = (field_digits(a, self.p, self.k), field_digits(b, self.p, self.k));
+        self.encode(a.iter().zip(b.iter()).map(|(x, y)| (x + y) % self.p))
+    }
+
+    fn neg(&self, a: usize) -> usize {
+        self.encode(
+            field_digits(a, self.p, self.k)
+                .into_iter()
+                .map(|x| (self.p - x) % self.p),
+        )
+    }
+
+    fn mul(&self, a: usize, b: usize) -> usize {
+        let (a, b) = (field_digits(a, self.p, self.k), field_digits(b, self.p, self.k));
+        let mut product = vec![0; 2 * self.k];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                product[i + j] = (product[i + j] + x * y) % self.p;
+            }
+        }
+
+        // Replace x^k by minus the rest of the modulus, from the top degree down
+        for d in (self.k..2 * self.k).rev() {
+            let c = product[d];
+            for (i, m) in self.modulus.iter().enumerate() {
+                product[d - self.k + i] = (product[d - self.k + i] + (self.p - m) * c) % self.p;
+            }
+        }
+
+        self.encode(product.into_iter().take(self.k))
+    }
+
+    fn inv(&self, a: usize) -> usize {
+        (1..self.q).find(|&b| self.mul(a, b) == 1).unwrap()
+    }
+
+    fn multiplicative_order(&self, g: usize) -> Option<usize> {
+        let mut power = g;
+        for order in 1..self.q {
+            if power == 1 {
+                return Some(order);
+            }
+            power = self.mul(power, g);
+        }
+        None
+    }
+
+    fn encode(&self, digits: impl DoubleEndedIterator<Item = usize>) -> usize {
+        digits.rev().fold(0, |x, d| x * self.p + d)
+    }
+
+    // The permutation of the projective line, whose point at infinity is q, extending a map that fixes it
+    fn mobius(&self, f: impl Fn(usize) -> usize) -> DefaultPermutation {
+        let images: Vec<_> = (0..self.q).map(f).chain(std::iter::once(self.q)).collect();
+        DefaultPermutation::from_images(&images)
+    }
+
+    // The map x -> -1/x of the projective line, which swaps 0 and infinity
+    fn inversion(&self) -> DefaultPermutation {
+        let images: Vec<_> = std::iter::once(self.q)
+            .chain((1..self.q).map(|x| self.neg(self.inv(x))))
+            .chain(std::iter::once(0))
+            .collect();
+        DefaultPermutation::from_images(&images)
+    }
+}
+
+// The k digits of x in base p, starting from the least significant one
+fn field_digits(x: usize, p: usize, k: usize) -> Vec<usize> {
+    (0..k)
+        .scan(x, |x, _| {
+            let digit = *x % p;
+            *x /= p;
+            Some(digit)
+        })
+        .collect()
+}
+
 impl<P> From<Group<P>> for DecoratedGroup<P>
 where
     P: Permutation,
@@ -59,3 +344,83 @@ where
         Self::new(g, order)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_order(g: &DecoratedGroup) {
+        assert_eq!(&g.group().stabchain().order(), g.order());
+    }
+
+    #[test]
+    fn mathieu() {
+        for &n in [11, 12, 22, 23, 24].iter() {
+            let g = DecoratedGroup::mathieu(n);
+            assert_eq!(g.group().symmetric_super_order(), n);
+            check_order(&g);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_mathieu() {
+        DecoratedGroup::mathieu(10);
+    }
+
+    #[test]
+    fn finite_field() {
+        for &q in [2, 3, 4, 5, 8, 9, 16, 25, 27].iter() {
+            let field = FiniteField::new(q);
+            for a in 1..q {
+                assert_eq!(field.mul(a, field.inv(a)), 1);
+                assert_eq!(field.add(a, field.neg(a)), 0);
+                for b in 0..q {
+                    assert_eq!(field.mul(a, b), field.mul(b, a));
+                }
+            }
+            assert_eq!(field.multiplicative_order(field.primitive), Some(q - 1));
+        }
+    }
+
+    #[test]
+    fn linear_groups() {
+        for &q in [2, 3, 4, 5, 7, 8, 9, 11, 16, 25].iter() {
+            let psl = DecoratedGroup::psl_2(q);
+            assert_eq!(psl.group().symmetric_super_order(), q + 1);
+            check_order(&psl);
+            check_order(&DecoratedGroup::pgl_2(q));
+            check_order(&DecoratedGroup::agl_1(q));
+        }
+
+        // PSL(2, 4) and PSL(2, 5) are both A5
+        assert_eq!(DecoratedGroup::psl_2(4).order(), DecoratedGroup::psl_2(5).order());
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_a_prime_power() {
+        DecoratedGroup::psl_2(12);
+    }
+
+    #[test]
+    fn dicyclic() {
+        for n in 1..8 {
+            check_order(&DecoratedGroup::dicyclic(n));
+        }
+        for n in 3..7 {
+            check_order(&DecoratedGroup::generalized_quaternion(n));
+        }
+
+        // Unlike the dihedral group of the same order, the quaternions have a single involution
+        let involutions = |g: &Group| g.bruteforce_elements().iter().filter(|p| p.order() == 2).count();
+        assert_eq!(involutions(DecoratedGroup::generalized_quaternion(3).group()), 1);
+        assert_eq!(involutions(DecoratedGroup::generalized_quaternion(5).group()), 1);
+        assert_eq!(involutions(&Group::dihedral_2n(4)), 5);
+    }
+
+    #[test]
+    fn rubiks_cube() {
+        check_order(&DecoratedGroup::rubiks_cube());
+    }
+}
@@ -0,0 +1,276 @@
+//! Homomorphisms between permutation groups, defined by the images of the generators.
+//!
+//! Everything is computed using the graph of the homomorphism, i.e. the subgroup {(g, phi(g))} of the
+//! direct product, which is the group generated by the diagonal action of each generator with its image on the
+//! disjoint union of the two domains. A stabilizer chain of the graph with a base starting with the points of
+//! one of the domains allows to move between the two groups by sifting.
+
+use {
+    super::{
+        orbit::abstraction::SimpleTransversalResolver,
+        stabchain::{
+            base::selectors::{
+                adaptors::PartialFixedBaseSelector,
+                DefaultSelector,
+            },
+            builder::NaiveBuilderStrategy,
+            element_testing,
+            Stabchain,
+        },
+        Group,
+    },
+    crate::perm::{
+        actions::SimpleApplication,
+        Permutation,
+    },
+};
+
+/// A homomorphism from a group G to a group H, defined by the images of the generators of G
+#[derive(Debug, Clone)]
+pub struct GroupHomomorphism<P, Q>
+where
+    P: Permutation,
+{
+    source: Group<P>,
+    images: Vec<Q>,
+    // G acts on 0..source_degree, H is shifted to act on source_degree..source_degree + target_degree
+    source_degree: usize,
+    target_degree: usize,
+    // Chain of the graph with the base beginning with the points of G
+    source_chain: Stabchain<P, SimpleTransversalResolver>,
+    // Chain of the graph with the base beginning with the points of H
+    target_chain: Stabchain<P, SimpleTransversalResolver>,
+}
+
+impl<P, Q> GroupHomomorphism<P, Q>
+where
+    P: Permutation,
+    Q: Permutation,
+{
+    /// Create the homomorphism mapping the i-th generator of `source` to `images[i]`.
+    /// Note that this does not check that such a homomorphism exists, see `is_well_defined`.
+    pub fn new(source: &Group<P>, images: &[Q]) -> Self {
+        assert_eq!(
+            source.generators().len(),
+            images.len(),
+            "Every generator needs to have an image"
+        );
+
+        let source_degree = source.symmetric_super_order();
+        let target_degree = images.iter().flat_map(|q| q.lmp()).max().unwrap_or(0) + 1;
+
+        let graph: Group<P> = source
+            .generators()
+            .iter()
+            .zip(images.iter())
+            .map(|(g, q)| Self::join(source_degree, target_degree, g, q))
+            .collect();
+
+        let source_points: Vec<_> = (0..source_degree).collect();
+        let target_points: Vec<_> = (source_degree..source_degree + target_degree).collect();
+
+        Self {
+            source: source.clone(),
+            images: images.to_vec(),
+            source_degree,
+            target_degree,
+            source_chain: Self::chain_with_prefix(&graph, &source_points),
+            target_chain: Self::chain_with_prefix(&graph, &target_points),
+        }
+    }
+
+    /// The group the homomorphism is defined on
+    pub fn source(&self) -> &Group<P> {
+        &self.source
+    }
+
+    /// The images of the generators of the source group
+    pub fn images(&self) -> &[Q] {
+        &self.images[..]
+    }
+
+    /// Check that the images of the generators actually define a homomorphism.
+    /// This is the case iff the graph of the map is no bigger than the source group.
+    pub fn is_well_defined(&self) -> bool {
+        self.source_chain.order() == self.source.stabchain().order()
+    }
+
+    /// Compute the image of an element of the source group, or None if the element is not in the source group
+    pub fn image(&self, p: &P) -> Option<Q> {
+        if p.lmp().is_some_and(|lmp| lmp >= self.source_degree) {
+            return None;
+        }
+
+        let layers = self.prefix_len(&self.source_chain, 0..self.source_degree);
+        let residue = element_testing::sift(self.source_chain.iter().take(layers), p)?;
+        // The residue now fixes all of the source domain, otherwise p is not in the group
+        if (0..self.source_degree).any(|i| residue.apply(i) != i) {
+            return None;
+        }
+
+        Some(self.project_target(&residue.inv()))
+    }
+
+    /// Compute an element mapped to q, or None if q is not in the image
+    pub fn preimage(&self, q: &Q) -> Option<P> {
+        if q.lmp().is_some_and(|lmp| lmp >= self.target_degree) {
+            return None;
+        }
+
+        let layers = self.prefix_len(
+            &self.target_chain,
+            self.source_degree..self.source_degree + self.target_degree,
+        );
+        let embedded = Self::join(self.source_degree, self.target_degree, &P::id(), q);
+        let residue = element_testing::sift(self.target_chain.iter().take(layers), &embedded)?;
+        if (0..self.target_degree).any(|i| residue.apply(self.source_degree + i) != self.source_degree + i) {
+            return None;
+        }
+
+        Some(self.project_source(&residue.inv()))
+    }
+
+    /// The image of the homomorphism, as a subgroup of the target
+    pub fn image_group(&self) -> Group<Q> {
+        self.images.iter().cloned().collect()
+    }
+
+    /// The kernel of the homomorphism, as a subgroup of the source
+    pub fn kernel(&self) -> Group<P> {
+        let layers = self.prefix_len(
+            &self.target_chain,
+            self.source_degree..self.source_degree + self.target_degree,
+        );
+        match self.target_chain.layer(layers) {
+            Some(record) => record
+                .group()
+                .generators()
+                .iter()
+                .map(|g| self.project_source(g))
+                .collect(),
+            None => Group::new(&[]),
+        }
+    }
+
+    /// Build the element acting as g on the source domain and as q shifted on the target domain
+    fn join(source_degree: usize, target_degree: usize, g: &P, q: &Q) -> P {
+        let shifted = q.shift(source_degree);
+        let images: Vec<_> = (0..source_degree)
+            .map(|i| g.apply(i))
+            .chain((source_degree..source_degree + target_degree).map(|i| shifted.apply(i)))
+            .collect();
+        P::from_images(&images[..])
+    }
+
+    fn project_source(&self, p: &P) -> P {
+        let images: Vec<_> = (0..self.source_degree).map(|i| p.apply(i)).collect();
+        P::from_images(&images[..])
+    }
+
+    fn project_target(&self, p: &P) -> Q {
+        let images: Vec<_> = (0..self.target_degree)
+            .map(|i| p.apply(self.source_degree + i) - self.source_degree)
+            .collect();
+        Q::from_images(&images[..])
+    }
+
+    fn chain_with_prefix(graph: &Group<P>, prefix: &[usize]) -> Stabchain<P, SimpleTransversalResolver> {
+        graph.stabchain_with_strategy(NaiveBuilderStrategy::new(
+            SimpleApplication::default(),
+            PartialFixedBaseSelector::new(prefix, DefaultSelector::default()),
+        ))
+    }
+
+    /// Number of layers of the chain whose base point is in the given range
+    fn prefix_len(&self, chain: &Stabchain<P, SimpleTransversalResolver>, range: std::ops::Range<usize>) -> usize {
+        chain.iter().take_while(|record| range.contains(record.base())).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::{
+            export::CyclePermutation,
+            DefaultPermutation,
+        },
+    };
+
+    // The sign homomorphism S_n -> C_2
+    fn sign(n: usize) -> GroupHomomorphism<DefaultPermutation, DefaultPermutation> {
+        let g = Group::symmetric(n);
+        let transposition: DefaultPermutation = CyclePermutation::single_cycle(&[1, 2]).into();
+        // The generators are (1 2) and (1 2 ... n)
+        let cycle_image = if n.is_multiple_of(2) {
+            transposition.clone()
+        } else {
+            DefaultPermutation::id()
+        };
+        let images = vec![transposition, cycle_image];
+        GroupHomomorphism::new(&g, &images)
+    }
+
+    #[test]
+    fn sign_homomorphism() {
+        let phi = sign(5);
+        assert!(phi.is_well_defined());
+        assert_eq!(
+            phi.kernel().stabchain().order(),
+            Group::alternating(5).stabchain().order()
+        );
+        assert_eq!(phi.image_group().stabchain().order(), 2u8.into());
+
+        let three_cycle: DefaultPermutation = CyclePermutation::single_cycle(&[1, 2, 3]).into();
+        assert!(phi.image(&three_cycle).unwrap().is_id());
+        let four_cycle: DefaultPermutation = CyclePermutation::single_cycle(&[1, 2, 3, 4]).into();
+        assert!(!phi.image(&four_cycle).unwrap().is_id());
+    }
+
+    #[test]
+    fn images_are_consistent() {
+        // S_4 acting on the 3 partitions of {1, 2, 3, 4} into pairs, giving S_4 -> S_3
+        let g = Group::symmetric(4);
+        let images: Vec<DefaultPermutation> = vec![
+            CyclePermutation::single_cycle(&[2, 3]).into(),
+            CyclePermutation::single_cycle(&[1, 3]).into(),
+        ];
+        let phi = GroupHomomorphism::new(&g, &images);
+        assert!(phi.is_well_defined());
+        assert_eq!(phi.kernel().stabchain().order(), 4u8.into());
+        assert_eq!(phi.image_group().stabchain().order(), 6u8.into());
+
+        let mut rng = g.rng();
+        for _ in 0..20 {
+            let a = rng.random_permutation();
+            let b = rng.random_permutation();
+            let image = phi.image(&a.multiply(&b)).unwrap();
+            assert_eq!(image, phi.image(&a).unwrap().multiply(&phi.image(&b).unwrap()));
+
+            let preimage = phi.preimage(&image).unwrap();
+            assert_eq!(phi.image(&preimage).unwrap(), image);
+        }
+    }
+
+    #[test]
+    fn elements_outside_of_the_groups() {
+        let phi = sign(4);
+        let outside: DefaultPermutation = CyclePermutation::single_cycle(&[1, 5]).into();
+        assert_eq!(phi.image(&outside), None);
+
+        let g = Group::alternating(4);
+        let images: Vec<_> = g.generators().iter().map(|_| DefaultPermutation::id()).collect();
+        let trivial = GroupHomomorphism::new(&g, &images);
+        let transposition: DefaultPermutation = CyclePermutation::single_cycle(&[1, 2]).into();
+        assert_eq!(trivial.image(&transposition), None);
+        assert_eq!(trivial.preimage(&transposition), None);
+        assert_eq!(trivial.kernel().stabchain().order(), 12u8.into());
+    }
+
+    #[test]
+    fn not_well_defined() {
+        let g = Group::cyclic(4);
+        let images: Vec<DefaultPermutation> = vec![CyclePermutation::single_cycle(&[1, 2, 3]).into()];
+        assert!(!GroupHomomorphism::new(&g, &images).is_well_defined());
+    }
+}
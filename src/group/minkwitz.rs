@@ -0,0 +1,326 @@
+//! Short words for group elements, using Minkwitz's algorithm.
+//!
+//! The sifting procedure of a stabilizer chain can be used to write an element as a word in the
+//! generators, but the resulting words tend to be extremely long. Minkwitz's algorithm instead
+//! fills a table of transversal elements by enumerating short words in the generators, always keeping
+//! the shortest word found for each coset. The words produced by sifting through this table
+//! are much shorter (i.e. it can be used to solve the Rubik's cube in a reasonable number of moves).
+
+use {
+    super::{
+        orbit::transversal::Transversal,
+        Group,
+    },
+    crate::{
+        perm::Permutation,
+        DetHashMap,
+    },
+};
+
+/// A word in the generators of a group, as a list of (generator, exponent) pairs, exponents being either 1 or -1.
+pub type Word = Vec<(usize, isize)>;
+
+/// Evaluate a word on a list of generators
+pub fn evaluate_word<P: Permutation>(word: &[(usize, isize)], gens: &[P]) -> P {
+    word.iter().fold(P::id(), |acc, &(i, e)| acc.multiply(&gens[i].pow(e)))
+}
+
+/// Remove all the adjacent occurrences of a letter and its inverse in a word
+pub fn free_reduce(word: &[(usize, isize)]) -> Word {
+    let mut reduced: Word = Vec::with_capacity(word.len());
+    for &letter in word {
+        match reduced.last() {
+            Some(&(i, e)) if i == letter.0 && e == -letter.1 => {
+                reduced.pop();
+            }
+            _ => reduced.push(letter),
+        }
+    }
+    reduced
+}
+
+fn inverse_word(word: &[(usize, isize)]) -> impl Iterator<Item = (usize, isize)> + '_ {
+    word.iter().rev().map(|&(i, e)| (i, -e))
+}
+
+/// A table of transversal elements, each with a short word in the generators, computed with Minkwitz's algorithm.
+/// The table is computed once and can then be used to factorize any number of elements.
+#[derive(Debug, Clone)]
+pub struct MinkwitzTable<P> {
+    gens: Vec<P>,
+    base: Vec<usize>,
+    // For each layer, maps a point in the orbit of the base point to a transversal element and its word
+    table: Vec<DetHashMap<usize, (P, Word)>>,
+    orbit_sizes: Vec<usize>,
+    max_len: usize,
+}
+
+impl<P> MinkwitzTable<P>
+where
+    P: Permutation,
+{
+    /// Compute the table with default parameters
+    pub fn new(g: &Group<P>) -> Self {
+        Self::with_parameters(g, 20000, 1000, 20)
+    }
+
+    /// Compute the table by enumerating `rounds` words, improving the table every `improve_every` rounds.
+    /// Words longer than `max_len` are discarded, where the bound is relaxed at every improvement
+    /// that fails to complete the table.
+    /// Entries which are still missing at the end are filled with the (long) words obtained from a stabilizer chain.
+    pub fn with_parameters(g: &Group<P>, rounds: usize, improve_every: usize, max_len: usize) -> Self {
+        assert!(improve_every > 0);
+        let chain = g.stabchain_with_words();
+        let base: Vec<usize> = chain.iter().map(|record| *record.base()).collect();
+        let orbit_sizes = chain.iter().map(|record| record.transversal().orbit().len()).collect();
+        let table = base
+            .iter()
+            .map(|&b| std::iter::once((b, (P::id(), Vec::new()))).collect())
+            .collect();
+
+        let mut minkwitz = Self {
+            gens: g.generators().to_vec(),
+            base,
+            table,
+            orbit_sizes,
+            max_len,
+        };
+
+        let mut words = ReducedWords::new(minkwitz.gens.len());
+        for round in 1..=rounds {
+            if minkwitz.is_complete() {
+                break;
+            }
+
+            let word = match words.next() {
+                Some(word) => word,
+                None => break,
+            };
+            let t = evaluate_word(&word, &minkwitz.gens);
+            minkwitz.step(0, t.inv(), inverse_word(&word).collect());
+            minkwitz.step(0, t, word);
+
+            if round % improve_every == 0 {
+                minkwitz.improve();
+                if !minkwitz.is_complete() {
+                    minkwitz.max_len = minkwitz.max_len * 5 / 4;
+                }
+            }
+        }
+
+        // Fill the holes with words from the chain, so that every element can be factorized
+        for (layer, record) in chain.iter().enumerate() {
+            let transversal = record.transversal();
+            for &point in transversal.orbit().iter() {
+                if minkwitz.table[layer].contains_key(&point) {
+                    continue;
+                }
+
+                let rep = transversal.representative(point).unwrap();
+                let word = free_reduce(&rep.slp().unwrap().word());
+                minkwitz.table[layer].insert(point, (rep.into_perm(), word));
+            }
+        }
+
+        minkwitz
+    }
+
+    /// Has an element with a short word been found for every coset
+    pub fn is_complete(&self) -> bool {
+        self.table
+            .iter()
+            .zip(self.orbit_sizes.iter())
+            .all(|(layer, &size)| layer.len() == size)
+    }
+
+    /// The base the table is computed with respect to
+    pub fn base(&self) -> &[usize] {
+        &self.base[..]
+    }
+
+    /// The length of the longest word in the table
+    pub fn max_word_len(&self) -> usize {
+        self.table
+            .iter()
+            .flat_map(|layer| layer.values())
+            .map(|(_, word)| word.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Write an element as a word in the generators of the group.
+    /// Returns None if the element is not in the group.
+    pub fn factorize(&self, p: &P) -> Option<Word> {
+        let mut t = p.clone();
+        let mut words = Vec::with_capacity(self.base.len());
+        for (layer, &b) in self.table.iter().zip(self.base.iter()) {
+            let (u, word) = layer.get(&t.apply(b))?;
+            t = t.divide(u);
+            words.push(word);
+        }
+
+        if !t.is_id() {
+            return None;
+        }
+
+        // p == u_k ... u_1
+        let word: Word = words.into_iter().rev().flatten().copied().collect();
+        Some(free_reduce(&word))
+    }
+
+    /// Sift the element t with word w starting from the given layer, replacing longer words in the table
+    fn step(&mut self, start: usize, mut t: P, mut w: Word) {
+        for layer in start..self.base.len() {
+            if w.len() > self.max_len {
+                return;
+            }
+
+            let point = t.apply(self.base[layer]);
+            match self.table[layer].get_mut(&point) {
+                None => {
+                    self.table[layer].insert(point, (t, w));
+                    return;
+                }
+                Some(entry) => {
+                    if w.len() < entry.1.len() {
+                        std::mem::swap(&mut entry.0, &mut t);
+                        std::mem::swap(&mut entry.1, &mut w);
+                    }
+
+                    t = t.divide(&entry.0);
+                    w.extend(inverse_word(&entry.1));
+                    w = free_reduce(&w);
+                }
+            }
+
+            if t.is_id() {
+                return;
+            }
+        }
+    }
+
+    /// Try to find shorter words by combining elements of the same layer
+    fn improve(&mut self) {
+        for layer in 0..self.base.len() {
+            let entries: Vec<_> = self.table[layer].values().cloned().collect();
+            for (t1, w1) in &entries {
+                for (t2, w2) in &entries {
+                    if w1.len() + w2.len() > self.max_len || (w1.is_empty() && w2.is_empty()) {
+                        continue;
+                    }
+
+                    let w: Word = free_reduce(&w1.iter().chain(w2.iter()).copied().collect::<Vec<_>>());
+                    self.step(layer, t1.multiply(t2), w);
+                }
+            }
+        }
+    }
+}
+
+/// Enumerates the freely reduced words over n generators in order of length
+struct ReducedWords {
+    n: usize,
+    // Letter 2i is the generator i, letter 2i + 1 its inverse
+    current: Vec<usize>,
+}
+
+impl ReducedWords {
+    fn new(n: usize) -> Self {
+        Self { n, current: Vec::new() }
+    }
+
+    fn is_reduced(&self) -> bool {
+        self.current.windows(2).all(|w| w[0] != w[1] ^ 1)
+    }
+
+    fn advance(&mut self) {
+        for letter in self.current.iter_mut().rev() {
+            *letter += 1;
+            if *letter < 2 * self.n {
+                return;
+            }
+            *letter = 0;
+        }
+        self.current.push(0);
+    }
+}
+
+impl Iterator for ReducedWords {
+    type Item = Word;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.n == 0 {
+            return None;
+        }
+
+        self.advance();
+        while !self.is_reduced() {
+            self.advance();
+        }
+
+        Some(
+            self.current
+                .iter()
+                .map(|&l| (l / 2, if l % 2 == 0 { 1 } else { -1 }))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::DefaultPermutation,
+    };
+
+    #[test]
+    fn reduced_words_enumeration() {
+        let words: Vec<_> = ReducedWords::new(2).take(4 + 12).collect();
+        assert_eq!(words[0], vec![(0, 1)]);
+        assert_eq!(words[3], vec![(1, -1)]);
+        assert!(words[4..].iter().all(|w| w.len() == 2 && free_reduce(w) == *w));
+    }
+
+    #[test]
+    fn free_reduction() {
+        assert_eq!(free_reduce(&[(0, 1), (1, 1), (1, -1), (0, -1)]), vec![]);
+        assert_eq!(free_reduce(&[(0, 1), (1, 1), (0, -1)]), vec![(0, 1), (1, 1), (0, -1)]);
+    }
+
+    #[test]
+    fn short_factorization_symmetric() {
+        let g = Group::symmetric(8);
+        let table = MinkwitzTable::new(&g);
+        assert!(table.is_complete());
+        let mut rng = g.rng();
+        for _ in 0..20 {
+            let p = rng.random_permutation();
+            let word = table.factorize(&p).unwrap();
+            assert_eq!(evaluate_word(&word, g.generators()), p);
+        }
+    }
+
+    #[test]
+    fn short_factorization_non_element() {
+        let g = Group::alternating(6);
+        let table = MinkwitzTable::new(&g);
+        let p = DefaultPermutation::from_images(&[1, 0]);
+        assert_eq!(table.factorize(&p), None);
+    }
+
+    #[test]
+    fn short_factorization_rubiks_cube() {
+        let g = Group::rubiks_cube();
+        let table = MinkwitzTable::new(&g);
+        assert!(table.is_complete());
+        let mut rng = g.rng();
+        for _ in 0..5 {
+            let p = rng.random_permutation();
+            let word = table.factorize(&p).unwrap();
+            assert_eq!(evaluate_word(&word, g.generators()), p);
+            // The words obtained from sifting are way longer than this
+            assert!(word.len() < 300);
+        }
+    }
+}
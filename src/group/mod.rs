@@ -1,7 +1,10 @@
 //! Mod with various operations and utilities for working with groups
 
+pub mod action_homomorphism;
 pub mod brute_force;
 pub mod group_library;
+pub mod homomorphism;
+pub mod minkwitz;
 pub mod orbit;
 pub mod random_perm;
 pub mod stabchain;
@@ -12,7 +15,6 @@ use {
         base::selectors::{
             adaptors::PartialFixedBaseSelector,
             DefaultSelector,
-            FixedBaseSelector,
         },
         builder::DefaultStrategy,
         Stabchain,
@@ -22,12 +24,17 @@ use {
             orbit::abstraction::TransversalResolver,
             stabchain::{
                 base::selectors::BaseSelector,
-                builder::BuilderStrategy,
+                builder::{
+                    BuilderStrategy,
+                    LimitError,
+                    Limits,
+                },
             },
         },
         perm::{
             actions::SimpleApplication,
             export::CyclePermutation,
+            impls::slp::SlpPermutation,
             utils::order_n_permutation,
             *,
         },
@@ -39,7 +46,10 @@ use serde::{
     Serialize,
 };
 
-use std::iter::FromIterator;
+use std::{
+    convert::TryFrom,
+    iter::FromIterator,
+};
 
 /// The main struct exported. It stores a group as a list of generators.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,7 +82,7 @@ impl Group {
             return Self::klein_4();
         }
 
-        let reflection_perm = if n % 2 == 0 {
+        let reflection_perm = if n.is_multiple_of(2) {
             let k = n / 2;
             // (1 2k)(2, 2k - 1)...(k, k+1)
             CyclePermutation::from_vec((1..=k).map(|i| vec![i, 2 * k - i + 1]).collect())
@@ -119,6 +129,60 @@ impl Group {
             order_n_permutation(1, n),
         ])
     }
+
+    /// The Rubik's cube group, acting on the 48 moving facelets of the cube.
+    /// The generators are the quarter turns of the faces, in the order U, L, F, R, B, D.
+    pub fn rubiks_cube() -> Self {
+        let faces: [[[usize; 4]; 5]; 6] = [
+            [
+                [1, 3, 8, 6],
+                [2, 5, 7, 4],
+                [9, 33, 25, 17],
+                [10, 34, 26, 18],
+                [11, 35, 27, 19],
+            ],
+            [
+                [9, 11, 16, 14],
+                [10, 13, 15, 12],
+                [1, 17, 41, 40],
+                [4, 20, 44, 37],
+                [6, 22, 46, 35],
+            ],
+            [
+                [17, 19, 24, 22],
+                [18, 21, 23, 20],
+                [6, 25, 43, 16],
+                [7, 28, 42, 13],
+                [8, 30, 41, 11],
+            ],
+            [
+                [25, 27, 32, 30],
+                [26, 29, 31, 28],
+                [3, 38, 43, 19],
+                [5, 36, 45, 21],
+                [8, 33, 48, 24],
+            ],
+            [
+                [33, 35, 40, 38],
+                [34, 37, 39, 36],
+                [3, 9, 46, 32],
+                [2, 12, 47, 29],
+                [1, 14, 48, 27],
+            ],
+            [
+                [41, 43, 48, 46],
+                [42, 45, 47, 44],
+                [14, 22, 30, 38],
+                [15, 23, 31, 39],
+                [16, 24, 32, 40],
+            ],
+        ];
+
+        faces
+            .iter()
+            .map(|cycles| CyclePermutation::from_vec(cycles.iter().map(|c| c.to_vec()).collect()).into_perm())
+            .collect()
+    }
 }
 
 impl<P> Group<P> {
@@ -220,6 +284,15 @@ where
         orbit::transversal::FactoredTransversal::new_with_action(self, base, &strat)
     }
 
+    /// Computes the permutation group induced by the action on the (finite) orbit of w,
+    /// together with the numbering of the points of the orbit
+    pub fn action_homomorphism<A>(&self, w: A::OrbitT, action: A) -> action_homomorphism::ActionHomomorphism<P, A>
+    where
+        A: Action<P>,
+    {
+        action_homomorphism::ActionHomomorphism::new(self, w, action)
+    }
+
     /// Computes a stabilizer chain for this group
     #[tracing::instrument]
     pub fn stabchain(&self) -> Stabchain<P, impl TransversalResolver<P>> {
@@ -230,13 +303,27 @@ where
     }
 
     /// Computes a stabilizer chain for this group with a base
+    ///
+    /// # Panics
+    /// Panics if the base is too short for the group, see [`Group::try_stabchain_base`].
     #[tracing::instrument]
     pub fn stabchain_base(&self, base: &[usize]) -> Stabchain<P, impl TransversalResolver<P>> {
-        Stabchain::new_with_strategy(
-            self,
-            DefaultStrategy::new(SimpleApplication::default(), FixedBaseSelector::new(base)),
-        )
+        self.try_stabchain_base(base).unwrap_or_else(|e| panic!("{}", e))
     }
+
+    /// Computes a stabilizer chain for this group with a base, or fails if the base is too short for the group
+    pub fn try_stabchain_base(
+        &self,
+        base: &[usize],
+    ) -> Result<Stabchain<P, impl TransversalResolver<P>>, crate::Error> {
+        // Points after the given base are only selected if the base does not suffice
+        let chain = self.stabchain_partial_base(base);
+        if chain.len() > base.len() {
+            return Err(crate::Error::BaseTooShort(base.len()));
+        }
+        Ok(chain)
+    }
+
     /// Computes a stabilizer chain for this group with a partial base, using the default strategy for further points.
     pub fn stabchain_partial_base(&self, partial_base: &[usize]) -> Stabchain<P, impl TransversalResolver<P>> {
         Stabchain::new_with_strategy(
@@ -253,11 +340,44 @@ where
         Stabchain::new_with_strategy(self, strat)
     }
 
+    /// Computes a stabilizer chain for this group with a strategy, failing if the construction exceeds the limits
+    pub fn try_stabchain_with_strategy<S: BuilderStrategy<P>>(
+        &self,
+        strat: S,
+        limits: Limits,
+    ) -> Result<Stabchain<P, S::Transversal, S::Action>, LimitError> {
+        Stabchain::try_new_with_strategy(self, strat, limits)
+    }
+
     /// Computes a stabilizer chain for this group with a chosen selector
     pub fn stabchain_with_selector(&self, selector: impl BaseSelector<P>) -> Stabchain<P, impl TransversalResolver<P>> {
         Stabchain::new_with_strategy(self, DefaultStrategy::new(SimpleApplication::default(), selector))
     }
 
+    /// Wrap each generator so that products of them keep track of the word they were computed from.
+    /// The i-th generator is mapped to the i-th generator symbol.
+    pub fn slp_generators(&self) -> Group<SlpPermutation<P>> {
+        Group::from_list(
+            self.generators
+                .iter()
+                .enumerate()
+                .map(|(i, g)| SlpPermutation::generator(g.clone(), i)),
+        )
+    }
+
+    /// Computes a stabilizer chain that can express its elements in terms of the generators of this group,
+    /// see `Stabchain::factorize`
+    pub fn stabchain_with_words(&self) -> Stabchain<SlpPermutation<P>, impl TransversalResolver<SlpPermutation<P>>> {
+        self.slp_generators().stabchain()
+    }
+
+    /// Write an element as a short word in the generators, as a list of (generator, exponent) pairs.
+    /// This computes a table with Minkwitz's algorithm, so if many elements need to be factorized
+    /// use `minkwitz::MinkwitzTable` directly. Returns None if the element is not in the group.
+    pub fn short_factorization(&self, p: &P) -> Option<minkwitz::Word> {
+        minkwitz::MinkwitzTable::new(self).factorize(p)
+    }
+
     /// Check G.subgroup_of(H) <=> G <= H
     #[tracing::instrument]
     pub fn subgroup_of(&self, h: &Group<P>) -> bool {
@@ -341,6 +461,158 @@ where
 
         it.collect()
     }
+
+    /// Computes the group generated by all the groups, each acting on its own points. The points of every group are
+    /// shifted past the `symmetric_super_order()` of the ones before it, see `disjoint_union_offsets`.
+    /// It is isomorphic to the direct product of the groups.
+    #[tracing::instrument]
+    pub fn disjoint_union_action(groups: &[Group<P>]) -> Group<P> {
+        let offsets = Self::disjoint_union_offsets(groups);
+        groups
+            .iter()
+            .zip(offsets)
+            .flat_map(|(g, offset)| g.generators().iter().map(move |p| p.shift(offset)))
+            .collect()
+    }
+
+    /// The offsets of the points of each group in their disjoint union action
+    pub fn disjoint_union_offsets(groups: &[Group<P>]) -> Vec<usize> {
+        groups
+            .iter()
+            .scan(0, |offset, g| {
+                let start = *offset;
+                *offset += g.symmetric_super_order();
+                Some(start)
+            })
+            .collect()
+    }
+
+    /// Maps an element of the disjoint union action of the groups back to the i-th group.
+    /// Returns `None` if there is no i-th group, or the element does not preserve the points of that group.
+    pub fn disjoint_union_projection(groups: &[Group<P>], i: usize, p: &P) -> Option<P> {
+        let offset = *Self::disjoint_union_offsets(groups).get(i)?;
+        restrict(p, offset, groups[i].symmetric_super_order())
+    }
+
+    /// Computes the diagonal action of the group on k copies of its points, where every element acts on all the
+    /// copies at once. The i-th copy is shifted by i times the `symmetric_super_order()`.
+    #[tracing::instrument]
+    pub fn diagonal_action(&self, k: usize) -> Group<P> {
+        let n = self.symmetric_super_order();
+        self.generators()
+            .iter()
+            .map(|g| (0..k).fold(P::id(), |diagonal, i| diagonal.multiply(&g.shift(i * n))))
+            .collect()
+    }
+
+    /// Maps an element of the diagonal action back to the group, through its action on the i-th copy of the points.
+    /// Returns `None` if the element does not preserve that copy.
+    pub fn diagonal_projection(&self, i: usize, p: &P) -> Option<P> {
+        let n = self.symmetric_super_order();
+        restrict(p, i * n, n)
+    }
+
+    /// Computes the direct power G^k, acting on k consecutive copies of the points of G
+    #[tracing::instrument]
+    pub fn direct_power(&self, k: usize) -> Group<P> {
+        (0..k).fold(Group::new(&[]), |power, _| Group::product(&power, self))
+    }
+
+    /// Computes the wreath product G wr H in its imprimitive action. H acts on its m = `symmetric_super_order()`
+    /// points, and the group acts on m blocks of the points of G, with H permuting the blocks.
+    /// The order is |G|^m |H|.
+    #[tracing::instrument]
+    pub fn wreath_product(&self, top: &Group<P>) -> Group<P> {
+        let n = self.symmetric_super_order();
+        let m = top.symmetric_super_order();
+
+        let blocks = top.generators().iter().map(|h| {
+            let images: Vec<_> = (0..n * m).map(|x| h.apply(x / n) * n + x % n).collect();
+            P::from_images(&images)
+        });
+
+        self.direct_power(m)
+            .generators()
+            .iter()
+            .cloned()
+            .chain(blocks)
+            .collect()
+    }
+
+    /// Computes the wreath product G wr H in its product action, on the n^m tuples of points of G, where n and m
+    /// are the `symmetric_super_order()` of G and H. The tuple (x_0, ..., x_m-1) is the point sum x_i n^i, every
+    /// coordinate is acted on by its copy of G, and H permutes the coordinates. The order is |G|^m |H| as long as
+    /// G is not trivial.
+    ///
+    /// # Panics
+    /// Panics if the degree n^m does not fit in a `usize`.
+    #[tracing::instrument]
+    pub fn wreath_product_product_action(&self, top: &Group<P>) -> Group<P> {
+        let n = self.symmetric_super_order();
+        let m = top.symmetric_super_order();
+        let degree = u32::try_from(m)
+            .ok()
+            .and_then(|m| n.checked_pow(m))
+            .unwrap_or_else(|| panic!("The product action of degree {}^{} is too large", n, m));
+        let digits = |x: usize| (0..m).map(move |i| x / n.pow(i as u32) % n);
+        let point = |digits: &[usize]| digits.iter().rev().fold(0, |x, &d| x * n + d);
+
+        let base = (0..m).flat_map(|i| {
+            self.generators().iter().map(move |g| {
+                let images: Vec<_> = (0..degree)
+                    .map(|x| {
+                        let mut tuple: Vec<_> = digits(x).collect();
+                        tuple[i] = g.apply(tuple[i]);
+                        point(&tuple)
+                    })
+                    .collect();
+                P::from_images(&images)
+            })
+        });
+
+        let top = top.generators().iter().map(|h| {
+            let images: Vec<_> = (0..degree)
+                .map(|x| {
+                    let mut tuple = vec![0; m];
+                    for (i, d) in digits(x).enumerate() {
+                        tuple[h.apply(i)] = d;
+                    }
+                    point(&tuple)
+                })
+                .collect();
+            P::from_images(&images)
+        });
+
+        base.chain(top).collect()
+    }
+
+    /// Computes the semidirect product N x| H, where H acts on N through `action`. The action maps every generator
+    /// of H to a permutation of the points of N that normalizes N, and has to extend to a homomorphism.
+    /// The group acts on the points of N, followed by a copy of the points of H, and its order is |N| |H|.
+    #[tracing::instrument(skip(action))]
+    pub fn semidirect_product(normal: &Group<P>, top: &Group<P>, action: impl Fn(&P) -> P) -> Group<P> {
+        let twisted: Vec<_> = top.generators().iter().map(|h| (action(h), h)).collect();
+        let n = twisted
+            .iter()
+            .flat_map(|(a, _)| a.lmp())
+            .map(|lmp| lmp + 1)
+            .fold(normal.symmetric_super_order(), usize::max);
+
+        normal
+            .generators()
+            .iter()
+            .cloned()
+            .chain(twisted.into_iter().map(|(a, h)| a.multiply(&h.shift(n))))
+            .collect()
+    }
+}
+
+// The action of p on offset..offset + degree, moved back to 0..degree, if p preserves these points
+fn restrict<P: Permutation>(p: &P, offset: usize, degree: usize) -> Option<P> {
+    let images = (offset..offset + degree)
+        .map(|x| p.apply(x).checked_sub(offset).filter(|&image| image < degree))
+        .collect::<Option<Vec<_>>>()?;
+    Some(P::from_images(&images))
 }
 
 impl<P> FromIterator<P> for Group<P>
@@ -375,6 +647,24 @@ where
 mod tests {
     use super::Group;
 
+    #[test]
+    fn try_stabchain_base() {
+        let g = Group::symmetric(5);
+        let chain = g.try_stabchain_base(&[0, 1, 2, 3]).unwrap();
+        assert_eq!(chain.base().base(), &[0, 1, 2, 3]);
+        assert_eq!(chain.order(), 120u8.into());
+        assert_eq!(
+            g.try_stabchain_base(&[0, 1, 2]).unwrap_err(),
+            crate::Error::BaseTooShort(3)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn stabchain_base_too_short() {
+        Group::symmetric(5).stabchain_base(&[0, 1, 2]);
+    }
+
     #[test]
     fn trivial_creation() {
         let _g = Group::trivial();
@@ -398,6 +688,13 @@ mod tests {
         assert_eq!(g.generators().len(), 2);
     }
 
+    #[test]
+    fn rubiks_cube_order() {
+        let g = Group::rubiks_cube();
+        assert_eq!(g.generators().len(), 6);
+        assert_eq!(g.stabchain().order(), 43252003274489856000u128.into());
+    }
+
     #[test]
     fn orbit_vs_factored_orbit() {
         use crate::group::orbit::transversal::Transversal;
@@ -426,6 +723,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::mutable_key_type)]
     fn test_product() {
         use crate::{
             perm::{
@@ -437,7 +735,7 @@ mod tests {
 
         let perm: DefaultPermutation = CyclePermutation::single_cycle(&[1, 2, 3]).into();
 
-        let g = Group::new(&[perm.clone()]);
+        let g = Group::new(std::slice::from_ref(&perm));
         let prod = Group::product(&g, &g);
 
         let gens: DetHashSet<_> = prod.generators().iter().cloned().collect();
@@ -454,6 +752,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::mutable_key_type)]
     fn random_regenerator() {
         use crate::DetHashSet;
 
@@ -481,4 +780,145 @@ mod tests {
         assert!(Group::alternating(10).subgroup_of(&Group::symmetric(10)));
         assert!(Group::trivial().subgroup_of(&Group::klein_4()));
     }
+
+    #[test]
+    fn direct_power_order() {
+        let g = Group::symmetric(3);
+        assert_eq!(g.direct_power(3).stabchain().order(), 216u32.into());
+        assert_eq!(g.direct_power(3).symmetric_super_order(), 9);
+        assert_eq!(g.direct_power(1).generators(), g.generators());
+        assert_eq!(g.direct_power(0).stabchain().order(), 1u32.into());
+    }
+
+    #[test]
+    fn wreath_product_order() {
+        use num::BigUint;
+
+        let cases = [
+            (Group::symmetric(3), Group::symmetric(2)),
+            (Group::symmetric(3), Group::cyclic(3)),
+            (Group::cyclic(2), Group::symmetric(4)),
+            (Group::dihedral_2n(4), Group::klein_4()),
+        ];
+        for (g, h) in cases.iter() {
+            let m = h.symmetric_super_order() as u32;
+            let expected = g.stabchain().order().pow(m) * h.stabchain().order();
+
+            let imprimitive = g.wreath_product(h);
+            assert_eq!(
+                imprimitive.symmetric_super_order(),
+                g.symmetric_super_order() * m as usize
+            );
+            assert_eq!(imprimitive.stabchain().order(), expected);
+
+            let product_action = g.wreath_product_product_action(h);
+            assert_eq!(product_action.symmetric_super_order(), g.symmetric_super_order().pow(m));
+            assert_eq!(product_action.stabchain().order(), expected);
+        }
+
+        // The hyperoctahedral group
+        let order: BigUint = Group::cyclic(2)
+            .wreath_product(&Group::symmetric(3))
+            .stabchain()
+            .order();
+        assert_eq!(order, 48u32.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "too large")]
+    fn wreath_product_product_action_too_large() {
+        Group::symmetric(10).wreath_product_product_action(&Group::symmetric(20));
+    }
+
+    #[test]
+    fn semidirect_product_order() {
+        use crate::perm::{
+            DefaultPermutation,
+            Permutation,
+        };
+
+        // AGL(1, p) as Z_p x| Z_p^*, with the multiplication by a primitive root
+        let multiplication =
+            |p: usize, r: usize| DefaultPermutation::from_images(&(0..p).map(|x| x * r % p).collect::<Vec<_>>());
+        let agl = Group::semidirect_product(&Group::cyclic(5), &Group::cyclic(4), |_| multiplication(5, 2));
+        assert_eq!(agl.stabchain().order(), 20u32.into());
+
+        // The non abelian group of order 21, as 2 has order 3 mod 7
+        let g = Group::semidirect_product(&Group::cyclic(7), &Group::cyclic(3), |_| multiplication(7, 2));
+        assert_eq!(g.stabchain().order(), 21u32.into());
+
+        // The dihedral group, with the inversion
+        let g = Group::semidirect_product(&Group::cyclic(6), &Group::cyclic(2), |_| multiplication(6, 5));
+        assert_eq!(g.stabchain().order(), 12u32.into());
+
+        // The trivial action gives the direct product
+        let g = Group::semidirect_product(&Group::symmetric(3), &Group::cyclic(4), |_| DefaultPermutation::id());
+        assert_eq!(g.stabchain().order(), 24u32.into());
+    }
+
+    #[test]
+    fn disjoint_union_action() {
+        use crate::perm::Permutation;
+
+        let groups = [Group::symmetric(4), Group::trivial(), Group::dihedral_2n(5)];
+        let union = Group::disjoint_union_action(&groups);
+        assert_eq!(Group::disjoint_union_offsets(&groups), vec![0, 4, 5]);
+        assert_eq!(union.stabchain().order(), (24u32 * 10).into());
+        assert_eq!(union.orbit(5).len(), 5);
+        assert_eq!(
+            Group::disjoint_union_action(&groups[..1]).generators(),
+            groups[0].generators()
+        );
+        assert_eq!(
+            Group::disjoint_union_action(&[Group::symmetric(4), Group::symmetric(3)]).generators(),
+            Group::product(&Group::symmetric(4), &Group::symmetric(3)).generators()
+        );
+
+        let mut rng = union.rng();
+        for _ in 0..20 {
+            let p = rng.random_permutation();
+            let projections: Vec<_> = (0..groups.len())
+                .map(|i| Group::disjoint_union_projection(&groups, i, &p).unwrap())
+                .collect();
+            assert!(projections[1].is_id());
+            for (g, q) in groups.iter().zip(projections.iter()) {
+                assert!(g.stabchain().in_group(q));
+            }
+            let rebuilt = projections[0].multiply(&projections[2].shift(5));
+            assert_eq!(rebuilt, p);
+        }
+        assert_eq!(
+            Group::disjoint_union_projection(&groups, groups.len(), &union.generators()[0]),
+            None
+        );
+    }
+
+    #[test]
+    fn diagonal_action() {
+        use crate::perm::Permutation;
+
+        let g = Group::symmetric(4);
+        let diagonal = g.diagonal_action(3);
+        assert_eq!(diagonal.symmetric_super_order(), 12);
+        assert_eq!(diagonal.stabchain().order(), 24u32.into());
+        assert_eq!(diagonal.orbit(5).len(), 4);
+        assert_eq!(g.diagonal_action(1).generators(), g.generators());
+        assert!(g.diagonal_action(0).generators().is_empty());
+
+        let mut rng = diagonal.rng();
+        for _ in 0..20 {
+            let p = rng.random_permutation();
+            let projection = g.diagonal_projection(0, &p).unwrap();
+            assert!((1..3).all(|i| g.diagonal_projection(i, &p).as_ref() == Some(&projection)));
+            assert_eq!(p.apply(9), projection.apply(1) + 8);
+        }
+
+        // A permutation swapping two copies does not preserve either of them
+        let swap = crate::perm::DefaultPermutation::from_images(&[4, 5, 6, 7, 0, 1, 2, 3]);
+        assert_eq!(g.diagonal_projection(0, &swap), None);
+        assert_eq!(
+            g.diagonal_projection(2, &swap),
+            Some(crate::perm::DefaultPermutation::id())
+        );
+    }
 }
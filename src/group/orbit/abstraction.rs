@@ -4,8 +4,8 @@
 use crate::{
     perm::{
         actions::SimpleApplication,
-        impls::word::WordPermutation,
         Action,
+        LazyPerm,
         Permutation,
     },
     DetHashMap,
@@ -13,6 +13,20 @@ use crate::{
 
 use std::fmt::Debug;
 
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The kinds of transversals a resolver can work with, used to tag exported stabilizer chains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TransversalKind {
+    /// Every point is mapped to its representative
+    Simple,
+    /// Every point is mapped to the generator labelling its edge in a Schreier tree
+    Factored,
+}
+
 /// A trait encapsulating the different ways in which a transversal can access a representative
 pub trait TransversalResolver<P, A = SimpleApplication<P>>: Default + Debug
 where
@@ -24,22 +38,25 @@ where
     /// Compute the representative
     fn representative(&self, map: &DetHashMap<A::OrbitT, P>, base: A::OrbitT, point: A::OrbitT) -> Option<P>;
 
-    /// Compute representative as word
+    /// Compute the representative as a lazy product, that is only evaluated when needed.
+    /// By default this wraps the representative, while factored transversals keep the labels of the Schreier tree.
     fn representative_as_word(
         &self,
         map: &DetHashMap<A::OrbitT, P>,
         base: A::OrbitT,
         point: A::OrbitT,
-    ) -> Option<WordPermutation<P>>
+    ) -> Option<LazyPerm<P>>
     where
         P: Permutation,
     {
-        self.representative(map, base, point)
-            .map(|p| WordPermutation::from_perm(&p))
+        self.representative(map, base, point).map(|p| LazyPerm::from_perm(&p))
     }
 
     /// Convert into a full blown transversal
     fn to_transversal(&self, map: DetHashMap<A::OrbitT, P>, base: A::OrbitT) -> Self::AssociatedTransversal;
+
+    /// The kind of transversal maps this resolver understands
+    fn kind(&self) -> TransversalKind;
 }
 
 /// A dispatcher which does simple lookups
@@ -60,6 +77,10 @@ where
     fn to_transversal(&self, map: DetHashMap<A::OrbitT, P>, base: A::OrbitT) -> Self::AssociatedTransversal {
         super::transversal::SimpleTransversal::from_raw(base, map, Self)
     }
+
+    fn kind(&self) -> TransversalKind {
+        TransversalKind::Simple
+    }
 }
 
 /// A dispatcher that does full Factored Transversal lookups
@@ -82,7 +103,7 @@ where
         map: &DetHashMap<A::OrbitT, P>,
         base: A::OrbitT,
         point: A::OrbitT,
-    ) -> Option<WordPermutation<P>>
+    ) -> Option<LazyPerm<P>>
     where
         P: Permutation,
     {
@@ -93,4 +114,8 @@ where
     fn to_transversal(&self, map: DetHashMap<A::OrbitT, P>, base: A::OrbitT) -> Self::AssociatedTransversal {
         super::transversal::FactoredTransversal::from_raw(base, map, Self(self.0.clone()))
     }
+
+    fn kind(&self) -> TransversalKind {
+        TransversalKind::Factored
+    }
 }
@@ -44,26 +44,19 @@ where
 {
     // Check if the element is in the orbit.
     if !transversal.contains_key(&point) {
-        None
-    } else {
-        let mut orbit_point = point.clone();
-        let mut rep = P::id();
-        // Move along the orbit till we reach a representative that the base moves to the point.
-        while orbit_point != base {
-            let g_inv = transversal.get(&orbit_point).unwrap();
-            rep = rep.multiply(g_inv);
-            orbit_point = strat.apply(g_inv, orbit_point);
-        }
-        // Invert at the end, as the inverses are used.
-        // If we want fgh, then we can instead do (h^-1, g^-1, f^-1)^-1.
-        debug_assert!(strat.apply(&rep.inv(), base) == point);
-        Some(rep.inv())
+        return None;
     }
     let mut orbit_point = point.clone();
     let mut rep = P::id();
+    let mut steps = 0;
     // Move along the orbit till we reach a representative that the base moves to the point.
     while orbit_point != base {
-        let g_inv = transversal.get(&orbit_point).unwrap();
+        // The path to the base visits every point at most once, unless the labels are corrupted
+        if steps == transversal.len() {
+            return None;
+        }
+        steps += 1;
+        let g_inv = transversal.get(&orbit_point)?;
         rep = rep.multiply(g_inv);
         orbit_point = strat.apply(g_inv, orbit_point);
     }
@@ -93,7 +86,10 @@ where
         let mut rep = vec![];
         // Move along the orbit till we reach a representative that the base moves to the point.
         while orbit_point != base {
-            let g_inv = transversal.get(&orbit_point).unwrap();
+            if rep.len() == transversal.len() {
+                return None;
+            }
+            let g_inv = transversal.get(&orbit_point)?;
             rep.push(g_inv.inv());
             orbit_point = strat.apply(g_inv, orbit_point);
         }
@@ -18,9 +18,9 @@ where
     pub(super) depth: DetHashMap<A::OrbitT, usize>,
 }
 
-impl<'a, P, A> Cube<P, A>
+impl<P, A> Cube<P, A>
 where
-    P: Permutation + 'a,
+    P: Permutation,
     A: Action<P>,
 {
     pub(super) fn new(base: A::OrbitT, seq: &[P], strat: &A, orbit_size: Option<usize>) -> Self {
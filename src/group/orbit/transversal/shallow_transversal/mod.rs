@@ -140,9 +140,15 @@ where
         let mut orbit_point = point.clone();
         // The +1 is because the base point has depth 0.
         let mut rep = WordPermutation::id_with_capacity(depth + 1);
+        let mut steps = 0;
         // Move along the orbit till we reach a representative that the base moves to the point.
         while orbit_point != base {
-            let g_inv = transversal.get(&orbit_point).unwrap();
+            // The path to the base visits every point at most once, unless the labels are corrupted
+            if steps == transversal.len() {
+                return None;
+            }
+            steps += 1;
+            let g_inv = transversal.get(&orbit_point)?;
             rep.multiply_mut(g_inv);
             orbit_point = strat.apply(g_inv, orbit_point);
         }
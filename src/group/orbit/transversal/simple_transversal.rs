@@ -74,7 +74,6 @@ where
 
 /// Computes the transversal of the base. It computes the orbit and the corresponding
 /// set of representatives
-
 // Needed since entry requires &mut
 #[allow(clippy::map_entry)]
 pub fn transversal<P, A>(g: &Group<P>, base: A::OrbitT, strat: &A) -> DetHashMap<A::OrbitT, P>
@@ -1,17 +1,27 @@
 pub mod selectors;
 
-use crate::perm::{
-    actions::SimpleApplication,
-    Action,
-    DefaultPermutation,
-    Permutation,
+use {
+    crate::perm::{
+        actions::SimpleApplication,
+        Action,
+        DefaultPermutation,
+        Permutation,
+    },
+    serde::{
+        Deserialize,
+        Serialize,
+    },
 };
-#[derive(Clone)]
+
+/// A base, only the points are exported since actions carry no data
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "A::OrbitT: Serialize", deserialize = "A::OrbitT: Deserialize<'de>"))]
 pub struct Base<P = DefaultPermutation, A = SimpleApplication<P>>
 where
     A: Action<P>,
 {
     points: Vec<A::OrbitT>,
+    #[serde(skip)]
     action: A,
 }
 
@@ -1,6 +1,9 @@
 use std::fmt::Debug;
 
-use super::BaseSelector;
+use {
+    super::BaseSelector,
+    crate::perm::actions::FromPoint,
+};
 
 /// A struct for partial base selectors
 #[derive(Debug, Clone)]
@@ -59,6 +62,30 @@ where
     }
 }
 
+/// Adapts a selector of points to actions on structures built over the points (i.e. sets or tuples),
+/// by selecting the structure built from the point the inner selector chooses
+#[derive(Debug, Clone, Default)]
+pub struct LiftedSelector<S> {
+    selector: S,
+}
+
+impl<S> LiftedSelector<S> {
+    /// Lift the given selector
+    pub fn new(selector: S) -> Self {
+        Self { selector }
+    }
+}
+
+impl<P, OrbitT, S> BaseSelector<P, OrbitT> for LiftedSelector<S>
+where
+    OrbitT: FromPoint,
+    S: BaseSelector<P, usize>,
+{
+    fn moved_point(&mut self, p: &P, pos: usize) -> OrbitT {
+        OrbitT::from_point(self.selector.moved_point(p, pos))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,6 +142,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lifted_lmp() {
+        use {
+            super::super::LmpSelector,
+            crate::perm::*,
+        };
+
+        let p = DefaultPermutation::from_images(&[1, 2, 3, 0]);
+        let mut selector = LiftedSelector::new(LmpSelector);
+        let set: Vec<usize> = selector.moved_point(&p, 0);
+        assert_eq!(set, vec![3]);
+        let pair: (usize, usize) = selector.moved_point(&p, 0);
+        assert_eq!(pair, (3, 3));
+    }
+
     #[test]
     fn partial_fixed_base_lmp() {
         use {
@@ -3,6 +3,7 @@ use super::BaseSelector;
 use std::fmt::Debug;
 
 /// A selector that chooses elements in order from a common base i.e. [1,2,3,4]
+/// Panics if the base is too short, see `Group::try_stabchain_base` for a fallible alternative.
 #[derive(Debug, Default, Clone)]
 pub struct FixedBaseSelector<T = usize> {
     base: Vec<T>,
@@ -1,5 +1,10 @@
 use {
-    super::Stabchain,
+    super::{
+        limits::LimitTracker,
+        LimitError,
+        Limits,
+        Stabchain,
+    },
     crate::{
         group::{
             orbit::{
@@ -39,6 +44,7 @@ where
     chain: Vec<StabchainRecord<P, FactoredTransversalResolver<A>, A>>,
     selector: S,
     action: A,
+    limits: LimitTracker,
 }
 
 impl<P, S, A> StabchainBuilderIft<P, S, A>
@@ -52,6 +58,7 @@ where
             chain: Vec::new(),
             selector,
             action,
+            limits: LimitTracker::default(),
         }
     }
 
@@ -80,18 +87,21 @@ where
     fn extend_inner(&mut self, p: P) {
         trace!(perm = %p, level = self.current_pos, "Extending with perm");
         // Note that id always in group
-        if element_testing::is_in_group(self.current_chain(), &p) {
+        if self.limits.interrupted() || element_testing::is_in_group(self.current_chain(), &p) {
             return;
         }
 
         // Bottom of the chain
         if self.bottom_of_the_chain() {
+            if self.limits.chain_exceeded(self.chain.len() + 1) {
+                return;
+            }
             debug!(level = self.current_pos, "Extending the chain at bottom");
             let moved_point = self.selector.moved_point(&p, self.current_pos);
             debug!(?moved_point, "Selected Moved Point");
             let mut record = StabchainRecord::new(
                 moved_point.clone(),
-                Group::new(&[p.clone()]),
+                Group::new(std::slice::from_ref(&p)),
                 [(moved_point.clone(), P::id())].iter().cloned().collect(),
             );
             trace!("Computing orbit of {:?}", moved_point);
@@ -102,6 +112,9 @@ where
                 next_orbit_point = self.action.apply(&p, next_orbit_point);
                 representative = representative.multiply(&p);
             }
+            if self.limits.orbit_exceeded(record.transversal.len()) {
+                return;
+            }
             debug!(record = ?record, level = self.current_pos, "Chain extended");
             self.chain.push(record);
             self.extend_lower_level(representative);
@@ -140,6 +153,9 @@ where
 
                 let new_perm = orbit_element_repr.multiply(&p).multiply(&image_repr.inv());
                 self.extend_lower_level(new_perm);
+                if self.limits.stopped() {
+                    return;
+                }
             } else {
                 new_transversal.insert(new_image, p.inv());
             }
@@ -150,6 +166,9 @@ where
 
         // Update the record
         record.transversal.extend(new_transversal);
+        if self.limits.orbit_exceeded(record.transversal.len()) {
+            return;
+        }
 
         // While we have orbit elements (and representatives to check)
         while let Some(orbit_element) = to_check.pop_back() {
@@ -180,10 +199,17 @@ where
                     // Extend lower level
                     let new_perm = orbit_element_repr.multiply(generator).multiply(&image_repr.inv());
                     self.extend_lower_level(new_perm);
+                    if self.limits.stopped() {
+                        return;
+                    }
                 } else {
                     // Store in transversal
                     record.transversal.insert(new_image.clone(), generator.inv());
 
+                    if self.limits.orbit_exceeded(record.transversal.len()) {
+                        return;
+                    }
+
                     // Update and ask to check the new image
                     to_check.push_back(new_image);
                 }
@@ -204,6 +230,10 @@ where
     A: Action<P>,
     S: BaseSelector<P, A::OrbitT>,
 {
+    fn set_limits(&mut self, limits: Limits) {
+        self.limits = LimitTracker::new(limits);
+    }
+
     fn set_generators(&mut self, gens: &Group<P>) {
         for gen in gens.generators() {
             self.current_pos = 0;
@@ -214,4 +244,9 @@ where
     fn build(self) -> Stabchain<P, FactoredTransversalResolver<A>, A> {
         Stabchain { chain: self.chain }
     }
+
+    fn try_build(self) -> Result<Stabchain<P, FactoredTransversalResolver<A>, A>, LimitError> {
+        self.limits.result()?;
+        Ok(self.build())
+    }
 }
@@ -0,0 +1,292 @@
+//! Limits on the resources used by the construction of a stabilizer chain.
+//!
+//! The builders check the limits as they go, and stop as soon as one is exceeded, so that a chain of a group that
+//! is too large for the limits is never completed. The orbit size and chain length are checked as the chain grows,
+//! the deadline and cancellation token every time a builder starts a new step.
+
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// The limits a construction has to respect. By default there are none.
+#[derive(Debug, Clone, Default)]
+pub struct Limits {
+    max_orbit_size: Option<usize>,
+    max_chain_length: Option<usize>,
+    deadline: Option<Instant>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl Limits {
+    /// Limits that are never exceeded, to be restricted with the other methods
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail if an orbit of the chain gets larger than this
+    pub fn max_orbit_size(mut self, size: usize) -> Self {
+        self.max_orbit_size = Some(size);
+        self
+    }
+
+    /// Fail if the chain gets more layers than this
+    pub fn max_chain_length(mut self, length: usize) -> Self {
+        self.max_chain_length = Some(length);
+        self
+    }
+
+    /// Fail if the construction is still running at this instant
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Fail if the construction takes longer than this, starting from now
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.deadline(Instant::now() + timeout)
+    }
+
+    /// Fail once the token is cancelled
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+}
+
+/// A token that can be used to cancel a construction, for example from another thread.
+/// Clones of a token share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// A token that has not been cancelled yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel all the constructions using this token
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Has the token been cancelled?
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The limit that stopped a construction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitError {
+    /// An orbit was larger than the maximum size
+    OrbitTooLarge(usize),
+    /// The chain was longer than the maximum length
+    ChainTooLong(usize),
+    /// The construction was still running at the deadline
+    DeadlineExceeded,
+    /// The cancellation token was cancelled
+    Cancelled,
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OrbitTooLarge(size) => write!(f, "an orbit has more than {} points", size),
+            Self::ChainTooLong(length) => write!(f, "the chain has more than {} layers", length),
+            Self::DeadlineExceeded => write!(f, "the deadline was exceeded"),
+            Self::Cancelled => write!(f, "the construction was cancelled"),
+        }
+    }
+}
+
+impl Error for LimitError {}
+
+/// Keeps track of the limits during a construction, remembering the first one that was exceeded.
+/// All the checks return whether the construction should stop.
+#[derive(Debug, Default)]
+pub(crate) struct LimitTracker {
+    limits: Limits,
+    error: Option<LimitError>,
+}
+
+impl LimitTracker {
+    pub(crate) fn new(limits: Limits) -> Self {
+        Self { limits, error: None }
+    }
+
+    fn fail(&mut self, error: LimitError) -> bool {
+        self.error.get_or_insert(error);
+        true
+    }
+
+    /// Has a limit been exceeded?
+    pub(crate) fn stopped(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Check the deadline and the cancellation token
+    pub(crate) fn interrupted(&mut self) -> bool {
+        if self.stopped() {
+            return true;
+        }
+
+        if matches!(&self.limits.cancellation, Some(token) if token.is_cancelled()) {
+            return self.fail(LimitError::Cancelled);
+        }
+        match self.limits.deadline {
+            Some(deadline) if Instant::now() >= deadline => self.fail(LimitError::DeadlineExceeded),
+            _ => false,
+        }
+    }
+
+    /// Check the size of an orbit
+    pub(crate) fn orbit_exceeded(&mut self, size: usize) -> bool {
+        match self.limits.max_orbit_size {
+            Some(max) if size > max => self.fail(LimitError::OrbitTooLarge(max)),
+            _ => self.stopped(),
+        }
+    }
+
+    /// Check the length of the chain
+    pub(crate) fn chain_exceeded(&mut self, length: usize) -> bool {
+        match self.limits.max_chain_length {
+            Some(max) if length > max => self.fail(LimitError::ChainTooLong(max)),
+            _ => self.stopped(),
+        }
+    }
+
+    /// The first limit that was exceeded, if any
+    pub(crate) fn result(&self) -> Result<(), LimitError> {
+        match &self.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            group::{
+                stabchain::{
+                    base::selectors::LmpSelector,
+                    builder::{
+                        random::parameters::RandomAlgoParameters,
+                        *,
+                    },
+                    valid_stabchain,
+                },
+                Group,
+            },
+            perm::actions::SimpleApplication,
+        },
+        num::BigUint,
+    };
+
+    // The order computed with each strategy, or the limit that was exceeded.
+    // The random strategies are given the order, so that they never stop with an incomplete chain.
+    #[allow(deprecated)]
+    fn orders(g: &Group, limits: Limits) -> Vec<Result<BigUint, LimitError>> {
+        let action = SimpleApplication::default;
+        let params = || RandomAlgoParameters::default().order(g.stabchain().order());
+        vec![
+            g.try_stabchain_with_strategy(NaiveBuilderStrategy::new(action(), LmpSelector), limits.clone())
+                .map(|chain| chain.order()),
+            g.try_stabchain_with_strategy(IftBuilderStrategy::new(action(), LmpSelector), limits.clone())
+                .map(|chain| chain.order()),
+            g.try_stabchain_with_strategy(
+                RandomBuilderStrategyNaive::new_with_params(action(), LmpSelector, params()),
+                limits.clone(),
+            )
+            .map(|chain| chain.order()),
+            g.try_stabchain_with_strategy(
+                RandomBuilderStrategyShallow::new_with_params(action(), LmpSelector, params()),
+                limits,
+            )
+            .map(|chain| chain.order()),
+        ]
+    }
+
+    #[test]
+    fn within_limits() {
+        let g = Group::symmetric(7);
+        let limits = Limits::new()
+            .max_orbit_size(7)
+            .max_chain_length(6)
+            .timeout(Duration::from_secs(600));
+        for order in orders(&g, limits.clone()) {
+            assert_eq!(order, Ok(BigUint::from(5040u32)));
+        }
+
+        let chain = g
+            .try_stabchain_with_strategy(
+                NaiveBuilderStrategy::new(SimpleApplication::default(), LmpSelector),
+                limits,
+            )
+            .unwrap();
+        valid_stabchain(&chain).unwrap();
+    }
+
+    #[test]
+    fn orbit_too_large() {
+        for order in orders(&Group::symmetric(7), Limits::new().max_orbit_size(5)) {
+            assert_eq!(order, Err(LimitError::OrbitTooLarge(5)));
+        }
+    }
+
+    #[test]
+    fn chain_too_long() {
+        let g = Group::product(&Group::symmetric(5), &Group::cyclic(3));
+        for order in orders(&g, Limits::new().max_chain_length(3)) {
+            assert_eq!(order, Err(LimitError::ChainTooLong(3)));
+        }
+    }
+
+    #[test]
+    fn cancelled() {
+        let token = CancellationToken::new();
+        let limits = Limits::new().cancellation_token(token.clone());
+        assert!(!token.is_cancelled());
+        token.cancel();
+        for order in orders(&Group::symmetric(50), limits) {
+            assert_eq!(order, Err(LimitError::Cancelled));
+        }
+    }
+
+    #[test]
+    fn deadline_exceeded() {
+        for order in orders(&Group::symmetric(50), Limits::new().deadline(Instant::now())) {
+            assert_eq!(order, Err(LimitError::DeadlineExceeded));
+        }
+    }
+
+    #[test]
+    fn cancel_from_other_thread() {
+        let token = CancellationToken::new();
+        let limits = Limits::new().cancellation_token(token.clone());
+        let handle = std::thread::spawn(move || {
+            Group::symmetric(2000)
+                .try_stabchain_with_strategy(
+                    NaiveBuilderStrategy::new(SimpleApplication::default(), LmpSelector),
+                    limits,
+                )
+                .map(|chain| chain.len())
+        });
+        token.cancel();
+        assert_eq!(handle.join().unwrap(), Err(LimitError::Cancelled));
+    }
+}
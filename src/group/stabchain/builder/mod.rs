@@ -27,9 +27,18 @@ use rand::{
 use std::fmt::Debug;
 
 mod ift;
+pub mod limits;
 mod naive;
+#[cfg(feature = "parallel")]
+mod parallel;
 pub mod random;
 
+pub use limits::{
+    CancellationToken,
+    LimitError,
+    Limits,
+};
+
 /// A builder is a datastructure to be used for constructing
 /// a stabilizer chain. While the ultimate record is the same for any kind of
 /// chain, there are some very real differences in performance that can occur
@@ -38,11 +47,17 @@ where
     A: Action<P>,
     P: Permutation,
 {
+    /// Set the limits the construction has to respect, before adding the generators
+    fn set_limits(&mut self, limits: Limits);
+
     /// Add the generators to be used for the construction
     fn set_generators(&mut self, gens: &Group<P>);
 
-    /// Build the stabilizer chain
+    /// Build the stabilizer chain. Should only be used if no limits were set
     fn build(self) -> Stabchain<P, V, A>;
+
+    /// Build the stabilizer chain, or fail with the first limit that was exceeded
+    fn try_build(self) -> Result<Stabchain<P, V, A>, LimitError>;
 }
 
 /// A strategy is a lightweight struct that allows to
@@ -98,6 +113,40 @@ where
     }
 }
 
+/// Deterministic Schreier Sims, computing orbits, Schreier generators and sifts in parallel.
+/// Requires the `parallel` feature, and permutations that can be shared between threads (i.e. `SyncPermutation`).
+#[cfg(feature = "parallel")]
+#[derive(Debug, Clone)]
+pub struct ParallelBuilderStrategy<A, S> {
+    selector: S,
+    action: A,
+}
+
+#[cfg(feature = "parallel")]
+impl<A, S> ParallelBuilderStrategy<A, S> {
+    /// Create the strategy
+    pub fn new(action: A, selector: S) -> Self {
+        Self { selector, action }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<P, S, A> BuilderStrategy<P> for ParallelBuilderStrategy<A, S>
+where
+    P: Permutation + Send + Sync,
+    A: Action<P> + Sync,
+    A::OrbitT: Send + Sync,
+    S: BaseSelector<P, A::OrbitT>,
+{
+    type Action = A;
+    type Transversal = SimpleTransversalResolver;
+    type BuilderT = parallel::StabchainBuilderParallel<P, S, A>;
+
+    fn make_builder(self) -> Self::BuilderT {
+        parallel::StabchainBuilderParallel::new(self.selector, self.action)
+    }
+}
+
 /// Schreir Sims with factored transversal. Much more memory friendly,
 /// yet much slower
 #[derive(Debug, Clone)]
@@ -169,7 +218,8 @@ impl<A, S, R> RandomBuilderStrategyNaive<A, S, R> {
 impl<P, S, A, R> BuilderStrategy<P> for RandomBuilderStrategyNaive<A, S, R>
 where
     P: Permutation,
-    A: Action<P, OrbitT = usize>,
+    A: Action<P>,
+    A::OrbitT: Ord,
     S: BaseSelector<P, A::OrbitT>,
     R: Rng + Debug,
 {
@@ -214,7 +264,8 @@ impl<A, S, R> RandomBuilderStrategyShallow<A, S, R> {
 impl<P, S, A, R> BuilderStrategy<P> for RandomBuilderStrategyShallow<A, S, R>
 where
     P: Permutation,
-    A: Action<P, OrbitT = usize>,
+    A: Action<P>,
+    A::OrbitT: Ord,
     S: BaseSelector<P, A::OrbitT>,
     R: Rng + Clone + Debug,
 {
@@ -1,6 +1,9 @@
 use {
     super::{
+        limits::LimitTracker,
         BaseSelector,
+        LimitError,
+        Limits,
         Stabchain,
     },
     crate::{
@@ -38,6 +41,7 @@ where
     chain: Vec<StabchainRecord<P, SimpleTransversalResolver, A>>,
     selector: S,
     action: A,
+    limits: LimitTracker,
 }
 
 impl<P, S, A> StabchainBuilderNaive<P, S, A>
@@ -51,6 +55,7 @@ where
             chain: Vec::new(),
             selector,
             action,
+            limits: LimitTracker::default(),
         }
     }
 
@@ -79,18 +84,21 @@ where
     fn extend_inner(&mut self, p: P) {
         trace!(perm = %p, level = self.current_pos, "Extending with perm");
         // Note that id always in group
-        if element_testing::is_in_group(self.current_chain(), &p) {
+        if self.limits.interrupted() || element_testing::is_in_group(self.current_chain(), &p) {
             return;
         }
 
         // Bottom of the chain
         if self.bottom_of_the_chain() {
+            if self.limits.chain_exceeded(self.chain.len() + 1) {
+                return;
+            }
             debug!(level = self.current_pos, "Extending the chain at bottom");
             let moved_point = self.selector.moved_point(&p, self.current_pos);
             debug!(?moved_point, "Selected Moved Point");
             let mut record = StabchainRecord::new(
                 moved_point.clone(),
-                Group::new(&[p.clone()]),
+                Group::new(std::slice::from_ref(&p)),
                 [(moved_point.clone(), P::id())].iter().cloned().collect(),
             );
 
@@ -104,6 +112,9 @@ where
                 next_orbit_point = self.action.apply(&p, next_orbit_point);
                 representative = representative.multiply(&p);
             }
+            if self.limits.orbit_exceeded(record.transversal.len()) {
+                return;
+            }
             debug!(record = ?record, level = self.current_pos, "Chain extended");
             self.chain.push(record);
             self.extend_lower_level(representative);
@@ -132,6 +143,9 @@ where
 
                 let new_perm = orbit_element_repr.multiply(&p).multiply(&image_repr.inv());
                 self.extend_lower_level(new_perm);
+                if self.limits.stopped() {
+                    return;
+                }
             } else {
                 new_transversal.insert(new_image, orbit_element_repr.multiply(&p));
             }
@@ -142,6 +156,9 @@ where
 
         // Update the record
         record.transversal.extend(new_transversal);
+        if self.limits.orbit_exceeded(record.transversal.len()) {
+            return;
+        }
 
         // While we have orbit elements (and representatives to check)
         while let Some((orbit_element, orbit_element_repr)) = to_check.pop_back() {
@@ -158,6 +175,9 @@ where
                     // Extend lower level
                     let new_perm = orbit_element_repr.multiply(generator).multiply(&image_repr.inv());
                     self.extend_lower_level(new_perm);
+                    if self.limits.stopped() {
+                        return;
+                    }
                 } else {
                     // Compute the repr s.t. repr^(orbit_element_repr * generator) = orbit_element ^ generator = new_image
                     let repr = orbit_element_repr.multiply(generator);
@@ -165,6 +185,10 @@ where
                     // Store in transversal
                     record.transversal.insert(new_image.clone(), repr.clone());
 
+                    if self.limits.orbit_exceeded(record.transversal.len()) {
+                        return;
+                    }
+
                     // Update and ask to check the new image
                     to_check.push_back((new_image, repr));
                 }
@@ -185,6 +209,10 @@ where
     A: Action<P>,
     S: BaseSelector<P, A::OrbitT>,
 {
+    fn set_limits(&mut self, limits: Limits) {
+        self.limits = LimitTracker::new(limits);
+    }
+
     fn set_generators(&mut self, gens: &Group<P>) {
         for gen in gens.generators() {
             self.current_pos = 0;
@@ -195,4 +223,9 @@ where
     fn build(self) -> Stabchain<P, SimpleTransversalResolver, A> {
         Stabchain { chain: self.chain }
     }
+
+    fn try_build(self) -> Result<Stabchain<P, SimpleTransversalResolver, A>, LimitError> {
+        self.limits.result()?;
+        Ok(self.build())
+    }
 }
@@ -0,0 +1,374 @@
+//! Deterministic Schreier Sims, where orbits, Schreier generators and sifts are computed in parallel.
+
+use {
+    super::{
+        limits::LimitTracker,
+        BaseSelector,
+        LimitError,
+        Limits,
+        Stabchain,
+    },
+    crate::{
+        group::{
+            orbit::abstraction::SimpleTransversalResolver,
+            stabchain::StabchainRecord,
+            Group,
+        },
+        perm::{
+            actions::SimpleApplication,
+            Action,
+            Permutation,
+        },
+        DetHashMap,
+    },
+    rayon::prelude::*,
+    std::ops::RangeInclusive,
+};
+
+use tracing::{
+    debug,
+    trace,
+};
+
+// A level of the chain while it is being built
+#[derive(Debug, Clone)]
+struct Level<P, T> {
+    base: T,
+    gens: Vec<P>,
+    // Computed lazily, and invalidated every time a generator is added
+    transversal: Option<DetHashMap<T, P>>,
+}
+
+// Helper struct, used to build the stabilizer chain
+#[derive(Debug)]
+pub struct StabchainBuilderParallel<P, S, A = SimpleApplication<P>>
+where
+    A: Action<P>,
+    P: Permutation,
+{
+    levels: Vec<Level<P, A::OrbitT>>,
+    selector: S,
+    action: A,
+    limits: LimitTracker,
+}
+
+impl<P, S, A> StabchainBuilderParallel<P, S, A>
+where
+    A: Action<P>,
+    P: Permutation,
+{
+    pub(super) fn new(selector: S, action: A) -> Self {
+        Self {
+            levels: Vec::new(),
+            selector,
+            action,
+            limits: LimitTracker::default(),
+        }
+    }
+}
+
+impl<P, S, A> StabchainBuilderParallel<P, S, A>
+where
+    P: Permutation + Send + Sync,
+    A: Action<P> + Sync,
+    A::OrbitT: Send + Sync,
+    S: BaseSelector<P, A::OrbitT>,
+{
+    /// Compute the transversal of the given level, one layer of the Schreier tree at a time.
+    /// The transversal is incomplete if a limit was exceeded.
+    fn orbit(level: &Level<P, A::OrbitT>, action: &A, limits: &mut LimitTracker) -> DetHashMap<A::OrbitT, P> {
+        let Level { base, gens, .. } = level;
+        let mut transversal: DetHashMap<_, _> = [(base.clone(), P::id())].iter().cloned().collect();
+        let mut frontier = vec![(base.clone(), P::id())];
+        while !frontier.is_empty() {
+            if limits.interrupted() || limits.orbit_exceeded(transversal.len()) {
+                break;
+            }
+
+            let images: Vec<_> = frontier
+                .par_iter()
+                .flat_map_iter(|(x, u)| gens.iter().map(move |g| (action.apply(g, x.clone()), u.multiply(g))))
+                .collect();
+
+            frontier.clear();
+            for (y, u) in images {
+                if !transversal.contains_key(&y) {
+                    transversal.insert(y.clone(), u.clone());
+                    frontier.push((y, u));
+                }
+            }
+        }
+
+        trace!(base = ?level.base, orbit_size = transversal.len(), "Computed orbit");
+        transversal
+    }
+
+    /// Sift p through the levels starting at `start`, returning the level where the sift failed together with the residue.
+    /// The level is the length of the chain if p sifted through yet is not the identity.
+    fn sift(levels: &[Level<P, A::OrbitT>], action: &A, start: usize, p: &P) -> Option<(usize, P)> {
+        let mut h = p.clone();
+        for (level, record) in levels.iter().enumerate().skip(start) {
+            let transversal = record.transversal.as_ref().expect("Lower levels should be complete");
+            let image = action.apply(&h, record.base.clone());
+            match transversal.get(&image) {
+                Some(u) => h = h.divide(u),
+                None => return Some((level, h)),
+            }
+        }
+
+        if h.is_id() {
+            None
+        } else {
+            Some((levels.len(), h))
+        }
+    }
+
+    /// Find the first Schreier generator of the given level that does not sift through the lower levels
+    fn failing_schreier_generator(&self, level: usize) -> Option<(usize, P)> {
+        let (levels, action) = (&self.levels[..], &self.action);
+        let Level { gens, transversal, .. } = &levels[level];
+        let transversal = transversal.as_ref().unwrap();
+        let reps: Vec<_> = transversal.iter().collect();
+        reps.par_iter()
+            .flat_map_iter(|&(x, u_x)| gens.iter().map(move |g| (x, u_x, g)))
+            .find_map_first(|(x, u_x, g)| {
+                let u_xg = &transversal[&action.apply(g, x.clone())];
+                let schreier_generator = u_x.multiply(g).divide(u_xg);
+                if schreier_generator.is_id() {
+                    return None;
+                }
+                Self::sift(levels, action, level + 1, &schreier_generator)
+            })
+    }
+
+    /// Add a new level whose base point is moved by p
+    fn push_level(&mut self, p: &P) {
+        let base = self.selector.moved_point(p, self.levels.len());
+        debug!(?base, level = self.levels.len(), "Selected Moved Point");
+        self.levels.push(Level {
+            base,
+            gens: Vec::new(),
+            transversal: None,
+        });
+    }
+
+    /// Add p to the generators of the given levels
+    fn add_generator(&mut self, levels: RangeInclusive<usize>, p: P) {
+        for record in &mut self.levels[levels] {
+            record.gens.push(p.clone());
+            record.transversal = None;
+        }
+    }
+
+    fn schreier_sims(&mut self) {
+        if self.levels.is_empty() {
+            return;
+        }
+
+        let mut level = self.levels.len() - 1;
+        loop {
+            if self.limits.interrupted() || self.limits.chain_exceeded(self.levels.len()) {
+                return;
+            }
+
+            if self.levels[level].transversal.is_none() {
+                let transversal = Self::orbit(&self.levels[level], &self.action, &mut self.limits);
+                if self.limits.orbit_exceeded(transversal.len()) {
+                    return;
+                }
+                self.levels[level].transversal = Some(transversal);
+            }
+
+            match self.failing_schreier_generator(level) {
+                Some((drop, h)) => {
+                    debug!(level, drop, "Schreier generator does not sift");
+                    if drop == self.levels.len() {
+                        self.push_level(&h);
+                    }
+                    self.add_generator(level + 1..=drop, h);
+                    level = drop;
+                }
+                None if level == 0 => return,
+                None => level -= 1,
+            }
+        }
+    }
+}
+
+impl<P, S, A> super::Builder<P, SimpleTransversalResolver, A> for StabchainBuilderParallel<P, S, A>
+where
+    P: Permutation + Send + Sync,
+    A: Action<P> + Sync,
+    A::OrbitT: Send + Sync,
+    S: BaseSelector<P, A::OrbitT>,
+{
+    fn set_limits(&mut self, limits: Limits) {
+        self.limits = LimitTracker::new(limits);
+    }
+
+    fn set_generators(&mut self, gens: &Group<P>) {
+        for gen in gens.generators().iter().filter(|g| !g.is_id()) {
+            // Make sure that no generator fixes the whole base
+            let moved = self
+                .levels
+                .iter()
+                .position(|record| self.action.apply(gen, record.base.clone()) != record.base);
+            let level = match moved {
+                Some(level) => level,
+                None => {
+                    self.push_level(gen);
+                    self.levels.len() - 1
+                }
+            };
+            self.add_generator(0..=level, gen.clone());
+        }
+
+        self.schreier_sims();
+    }
+
+    fn build(self) -> Stabchain<P, SimpleTransversalResolver, A> {
+        let chain = self
+            .levels
+            .into_iter()
+            .map(|record| {
+                StabchainRecord::new(
+                    record.base,
+                    Group::new(&record.gens),
+                    record.transversal.expect("All levels should be complete"),
+                )
+            })
+            .collect();
+        Stabchain { chain }
+    }
+
+    fn try_build(self) -> Result<Stabchain<P, SimpleTransversalResolver, A>, LimitError> {
+        self.limits.result()?;
+        Ok(self.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            group::{
+                orbit::abstraction::SimpleTransversalResolver,
+                stabchain::{
+                    base::selectors::{
+                        adaptors::LiftedSelector,
+                        FmpSelector,
+                        LmpSelector,
+                    },
+                    builder::{
+                        LimitError,
+                        Limits,
+                        ParallelBuilderStrategy,
+                    },
+                    valid_stabchain,
+                    Stabchain,
+                },
+                Group,
+            },
+            perm::{
+                actions::{
+                    OnPairs,
+                    SimpleApplication,
+                },
+                impls::sync::SyncPermutation,
+                utils::random_permutation,
+            },
+        },
+        num::BigUint,
+    };
+
+    fn sync_group(g: Group) -> Group<SyncPermutation> {
+        g.map(SyncPermutation::from)
+    }
+
+    fn parallel_chain(g: &Group<SyncPermutation>) -> Stabchain<SyncPermutation, SimpleTransversalResolver> {
+        g.stabchain_with_strategy(ParallelBuilderStrategy::new(SimpleApplication::default(), LmpSelector))
+    }
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    #[test]
+    fn trivial_chain() {
+        let chain = parallel_chain(&Group::new(&[]));
+        assert!(chain.is_empty());
+    }
+
+    #[test]
+    fn known_orders() {
+        let groups = vec![
+            (Group::symmetric(7), 5040u32),
+            (Group::alternating(8), 20160),
+            (Group::dihedral_2n(50), 100),
+            (Group::cyclic(100), 100),
+            (Group::klein_4(), 4),
+        ];
+        for (g, order) in groups {
+            let g = sync_group(g);
+            let chain =
+                g.stabchain_with_strategy(ParallelBuilderStrategy::new(SimpleApplication::default(), FmpSelector));
+            valid_stabchain(&chain).unwrap();
+            assert_eq!(chain.order(), BigUint::from(order));
+        }
+    }
+
+    #[test]
+    fn agrees_with_naive() {
+        let g = sync_group(Group::product(&Group::symmetric(4), &Group::dihedral_2n(5)));
+        let chain = parallel_chain(&g);
+        valid_stabchain(&chain).unwrap();
+        assert_eq!(chain.order(), g.stabchain().order());
+
+        for _ in 0..20 {
+            let p = random_permutation::<SyncPermutation>(14);
+            assert_eq!(chain.in_group(&p), g.stabchain().in_group(&p));
+        }
+    }
+
+    #[test]
+    fn rubiks_cube_chain() {
+        let g = sync_group(Group::rubiks_cube());
+        let chain = parallel_chain(&g);
+        assert_eq!(chain.order(), BigUint::from(43252003274489856000u128));
+    }
+
+    #[test]
+    fn on_pairs_chain() {
+        let g = sync_group(Group::symmetric(5));
+        let chain = g.stabchain_with_strategy(ParallelBuilderStrategy::new(
+            OnPairs::default(),
+            LiftedSelector::new(LmpSelector),
+        ));
+        valid_stabchain(&chain).unwrap();
+        assert_eq!(chain.order(), BigUint::from(120u32));
+    }
+
+    #[test]
+    fn limits_exceeded() {
+        let g = sync_group(Group::symmetric(7));
+        let strategy = ParallelBuilderStrategy::new(SimpleApplication::default(), LmpSelector);
+        let result = g.try_stabchain_with_strategy(strategy.clone(), Limits::new().max_orbit_size(5));
+        assert_eq!(result.map(|chain| chain.len()), Err(LimitError::OrbitTooLarge(5)));
+        let result = g.try_stabchain_with_strategy(strategy.clone(), Limits::new().max_chain_length(4));
+        assert_eq!(result.map(|chain| chain.len()), Err(LimitError::ChainTooLong(4)));
+
+        let chain = g
+            .try_stabchain_with_strategy(strategy, Limits::new().max_chain_length(6))
+            .unwrap();
+        assert_eq!(chain.order(), BigUint::from(5040u32));
+    }
+
+    #[test]
+    fn chain_is_send_sync() {
+        let g = sync_group(Group::symmetric(6));
+        let chain = parallel_chain(&g);
+        assert_send_sync(&chain);
+
+        let p: SyncPermutation = g.generators()[0].clone();
+        let handle = std::thread::spawn(move || chain.in_group(&p));
+        assert!(handle.join().unwrap());
+    }
+}
@@ -14,6 +14,11 @@ use {
             },
             stabchain::{
                 base::selectors::BaseSelector,
+                builder::{
+                    limits::LimitTracker,
+                    LimitError,
+                    Limits,
+                },
                 order,
                 Stabchain,
                 StabchainRecord,
@@ -54,7 +59,7 @@ use tracing::{
 #[derive(Debug)]
 pub struct StabchainBuilderRandom<P, S, A = SimpleApplication<P>, R = ThreadRng>
 where
-    A: Action<P, OrbitT = usize>,
+    A: Action<P>,
     P: Permutation,
 {
     current_pos: usize,
@@ -66,6 +71,7 @@ where
     base: Vec<A::OrbitT>,
     rng: RefCell<R>,
     constants: super::parameters::Constants,
+    limits: LimitTracker,
     //The chain is zero indexed, but this field is 1 indexed.
     //This is due to the end condition being when the chain is up to date below the index of the first record position, and this would be -1 with zero indexing.
     //For zero indexing this would have to be a signed type, which doesn't really seem worth it just to require one negative value at the end condition.
@@ -77,7 +83,8 @@ impl<P, S, A, R> StabchainBuilderRandom<P, S, A, R>
 where
     P: Permutation,
     S: BaseSelector<P, A::OrbitT>,
-    A: Action<P, OrbitT = usize>,
+    A: Action<P>,
+    A::OrbitT: Ord,
     R: Rng,
 {
     pub fn new(selector: S, action: A, params: RandomAlgoParameters<R>) -> Self {
@@ -92,6 +99,7 @@ where
             base: Vec::new(),
             constants,
             rng: RefCell::new(random),
+            limits: LimitTracker::default(),
             up_to_date: 1,
         }
     }
@@ -104,6 +112,14 @@ where
         Stabchain { chain: self.chain }
     }
 
+    /// Check the limits against the chain built so far
+    fn limits_exceeded(&mut self) -> bool {
+        let largest_orbit = self.chain.iter().map(|record| record.transversal.len()).max();
+        self.limits.interrupted()
+            || self.limits.chain_exceeded(self.chain.len())
+            || self.limits.orbit_exceeded(largest_orbit.unwrap_or(0))
+    }
+
     fn construct_strong_generating_set(&mut self, group: &Group<P>) {
         trace!(group = %group, "Constructing Strong Generating Set");
         //Edge case for trivial group.
@@ -113,24 +129,30 @@ where
         //Find the largest moved point of any generator, i.e find which size of the symmetric group the generators form a subgroup of.
         // The minus 1 is to account for this being zero indexed, e.g S_4 moves points 0..3.
         self.n = group.symmetric_super_order() - 1;
-        //Pick the initial moved point.
-        let moved_point = group
+        //Pick the smallest of the initial moved points selected for each generator, skipping the identity, as groups
+        //built with Group::from_list may contain it.
+        let candidates = group
             .generators()
             .iter()
-            .map(|p| self.selector.moved_point(p, 0))
-            .min()
-            .unwrap();
-        debug!(group = %group, moved_point = moved_point, "Adding initial record");
+            .filter(|g| !g.is_id())
+            .map(|g| self.selector.moved_point(g, 0));
+        let moved_point = match candidates.min() {
+            Some(moved_point) => moved_point,
+            None => return,
+        };
+        debug!(group = %group, ?moved_point, "Adding initial record");
         //Create the top level record for this chain, and add it to the chain.
         //TODO check if you should add generators 1 by 1, in case there are redundant generators.
         let initial_record = StabchainRecord::new(
-            moved_point,
+            moved_point.clone(),
             group.clone(),
-            factored_transversal_complete_opt(group, moved_point, &self.action),
+            factored_transversal_complete_opt(group, moved_point.clone(), &self.action),
         );
         self.base.push(moved_point);
         self.chain.push(initial_record);
         self.sgc();
+        // The last step might have grown the chain past the limits
+        self.limits_exceeded();
     }
 
     /// Generate a permutation that is with high probably a schrier generator for the current subgroup.
@@ -167,7 +189,8 @@ where
                 .keys()
                 .choose(&mut *self.rng.borrow_mut())
                 .map(|point| {
-                    representative_raw_as_word(&record.transversal, record.base, *point, &self.action).unwrap()
+                    representative_raw_as_word(&record.transversal, record.base.clone(), point.clone(), &self.action)
+                        .unwrap()
                 })
                 .expect("should be present")
         })
@@ -197,7 +220,7 @@ where
 
             // If we haven't seen this element.
             if !(record.transversal.contains_key(&new_image) || new_transversal.contains_key(&new_image)) {
-                new_transversal.insert(new_image, p.inv());
+                new_transversal.insert(new_image.clone(), p.inv());
             }
         }
 
@@ -212,9 +235,9 @@ where
             // Get the pair
             // For each generator (and p)
             for generator in std::iter::once(&p).chain(record.gens.generators()) {
-                let new_image = self.action.apply(generator, orbit_element);
+                let new_image = self.action.apply(generator, orbit_element.clone());
                 // If we haven't already seen the image
-                record.transversal.entry(new_image).or_insert_with(|| {
+                record.transversal.entry(new_image.clone()).or_insert_with(|| {
                     // Update and ask to check the new image
                     to_check.push_back(new_image);
                     generator.inv()
@@ -240,6 +263,9 @@ where
 
     fn sgc(&mut self) {
         trace!(level = self.current_pos, "Strong Generating Set Construction");
+        if self.limits_exceeded() {
+            return;
+        }
         let record = self.chain[self.current_pos].clone();
         //Number of base points than are in the current orbit.
         let b_star = self.base.iter().filter(|&b| record.transversal.contains_key(b)).count();
@@ -294,11 +320,11 @@ where
                     let new_base_point = self.selector.moved_point(&h_star, self.current_pos);
                     //self.check_transversal_augmentation(h_star);
                     debug_assert!(!self.base.contains(&new_base_point));
-                    debug!(perm = %h_star, moved_point = new_base_point, "Extending chain");
-                    self.base.push(new_base_point);
+                    debug!(perm = %h_star, moved_point = ?new_base_point, "Extending chain");
+                    self.base.push(new_base_point.clone());
                     //Fields for the new record.
                     let gens = Group::new(&[h_star]);
-                    let transversal = factored_transversal_complete_opt(&gens, new_base_point, &self.action);
+                    let transversal = factored_transversal_complete_opt(&gens, new_base_point.clone(), &self.action);
                     let record = StabchainRecord::new(new_base_point, gens, transversal);
                     self.chain.push(record);
                     //Now up to date beneath the newly added point.
@@ -333,6 +359,9 @@ where
     /// Test that the current strong generating set is indeed a strong generating set, returning true if it (probably) is.
     fn sgt(&mut self) {
         trace!("Strong Generating Test");
+        if self.limits_exceeded() {
+            return;
+        }
         let original_position = self.current_pos;
         //Should be at the top of the chain, I think.
         self.current_pos = 0;
@@ -391,10 +420,10 @@ where
             //If this point sifted through but isn't trivial, then we need a new record and base point.
             if self.sifted(drop_out_level) {
                 let moved_point = self.selector.moved_point(&collapsed_residue, self.current_pos);
-                debug!(perm = %collapsed_residue, moved_point = moved_point, "Extending chain");
+                debug!(perm = %collapsed_residue, ?moved_point, "Extending chain");
                 let gens = Group::new(&[collapsed_residue]);
-                let transversal = factored_transversal_complete_opt(&gens, moved_point, &self.action);
-                let initial_record = StabchainRecord::new(moved_point, gens, transversal);
+                let transversal = factored_transversal_complete_opt(&gens, moved_point.clone(), &self.action);
+                let initial_record = StabchainRecord::new(moved_point.clone(), gens, transversal);
                 self.base.push(moved_point);
                 self.chain.push(initial_record);
                 self.up_to_date = self.base.len() + 1;
@@ -410,8 +439,9 @@ where
     }
 
     /// Wrapper function to check all points of the permutation domain.
+    /// Note that this checks the permutation itself, and not its action.
     fn is_trivial_residue_all_points(&self, p_as_words: &[P]) -> bool {
-        self.is_trivial_residue(p_as_words, 0..self.n)
+        (0..self.n).all(|x| p_as_words.iter().fold(x, |accum, p| p.apply(accum)) == x)
     }
 
     /// Check if a residue acts trivially on a set of points.
@@ -419,7 +449,7 @@ where
     fn is_trivial_residue(&self, p_as_words: &[P], points: impl IntoIterator<Item = A::OrbitT>) -> bool {
         points
             .into_iter()
-            .all(|x| apply_permutation_word(p_as_words, x, &self.action) == x)
+            .all(|x| apply_permutation_word(p_as_words, x.clone(), &self.action) == x)
     }
 
     //Utility function to check if a given drop out level is the bottom of the chain.
@@ -432,10 +462,15 @@ impl<P, S, A, R> crate::group::stabchain::builder::Builder<P, FactoredTransversa
     for StabchainBuilderRandom<P, S, A, R>
 where
     P: Permutation,
-    A: Action<P, OrbitT = usize>,
+    A: Action<P>,
+    A::OrbitT: Ord,
     S: BaseSelector<P, A::OrbitT>,
     R: Rng + Debug,
 {
+    fn set_limits(&mut self, limits: Limits) {
+        self.limits = LimitTracker::new(limits);
+    }
+
     fn set_generators(&mut self, gens: &Group<P>) {
         self.construct_strong_generating_set(gens);
     }
@@ -443,6 +478,11 @@ where
     fn build(self) -> Stabchain<P, FactoredTransversalResolver<A>, A> {
         self.build()
     }
+
+    fn try_build(self) -> Result<Stabchain<P, FactoredTransversalResolver<A>, A>, LimitError> {
+        self.limits.result()?;
+        Ok(self.build())
+    }
 }
 
 // Functions used for compatability reasons
@@ -11,6 +11,11 @@ use {
             },
             stabchain::{
                 base::selectors::BaseSelector,
+                builder::{
+                    limits::LimitTracker,
+                    LimitError,
+                    Limits,
+                },
                 element_testing::residue_as_words_from_words,
                 order,
                 Stabchain,
@@ -62,7 +67,7 @@ use tracing::{
 #[derive(Debug)]
 pub struct StabchainBuilderRandomSTrees<P, S, A = SimpleApplication<P>, R = ThreadRng>
 where
-    A: Action<P, OrbitT = usize>,
+    A: Action<P>,
     R: rand::Rng,
     P: Permutation,
 {
@@ -78,13 +83,15 @@ where
     max_depths: Vec<usize>,
     original_generators: Group<P>,
     constants: super::parameters::Constants,
+    limits: LimitTracker,
 }
 
 impl<P, S, A, R> StabchainBuilderRandomSTrees<P, S, A, R>
 where
     P: Permutation,
     S: BaseSelector<P, A::OrbitT>,
-    A: Action<P, OrbitT = usize>,
+    A: Action<P>,
+    A::OrbitT: Ord,
     R: Rng + Clone,
 {
     pub fn new(selector: S, action: A, params: RandomAlgoParameters<R>) -> Self {
@@ -100,6 +107,7 @@ where
             max_depths: Vec::new(),
             original_generators: Group::new(&[]),
             constants,
+            limits: LimitTracker::default(),
         }
     }
 
@@ -118,6 +126,14 @@ where
         Stabchain { chain: self.chain }
     }
 
+    /// Check the limits against the chain built so far
+    fn limits_exceeded(&mut self) -> bool {
+        let largest_orbit = self.chain.iter().map(|record| record.transversal.len()).max();
+        self.limits.interrupted()
+            || self.limits.chain_exceeded(self.chain.len())
+            || self.limits.orbit_exceeded(largest_orbit.unwrap_or(0))
+    }
+
     fn construct_strong_generating_set(&mut self, group: &Group<P>) {
         trace!(group = %group, "Constructing Strong Generating Set");
         //Edge case for trivial group.
@@ -127,24 +143,28 @@ where
         //Find the largest moved point of any generator, i.e find which size of the symmetric group the generators form a subgroup of.
         // The minus 1 is to account for this being zero indexed, e.g S_4 moves points 0..3.
         self.n = group.symmetric_super_order() - 1;
-        //Pick the initial moved point.
-        let moved_point = group
+        //Pick the smallest of the initial moved points selected for each generator, skipping the identity, as groups
+        //built with Group::from_list may contain it.
+        let candidates = group
             .generators()
             .iter()
-            .map(|p| self.selector.moved_point(p, 0))
-            .min()
-            .unwrap();
+            .filter(|g| !g.is_id())
+            .map(|g| self.selector.moved_point(g, 0));
+        let moved_point = match candidates.min() {
+            Some(moved_point) => moved_point,
+            None => return,
+        };
         //Create the top level record for this chain, and add it to the chain.
         let mut initial_gens = group.clone();
         self.original_generators = initial_gens.clone();
         let (transversal, initial_depth) = shallow_transversal(
             &mut initial_gens,
-            moved_point,
+            moved_point.clone(),
             &self.action,
             &mut *self.rng.borrow_mut(),
         );
-        debug!(group = %initial_gens, moved_point = moved_point, orbit=?transversal.keys(), "Adding initial record");
-        let initial_record = StabchainRecord::new(moved_point, initial_gens, transversal);
+        debug!(group = %initial_gens, ?moved_point, orbit=?transversal.keys(), "Adding initial record");
+        let initial_record = StabchainRecord::new(moved_point.clone(), initial_gens, transversal);
         self.base.push(moved_point);
         self.max_depths.push(*initial_depth.values().max().unwrap());
         self.depths.push(initial_depth);
@@ -153,12 +173,17 @@ where
         let mut invoke_opt = Some(0);
         // Repeat until the SGT passes, i.e gives no level invoke on
         while let Some(invoke_level) = invoke_opt {
+            if self.limits_exceeded() {
+                return;
+            }
             // If sgc is passed, then we also need to pass the strong generating test
             invoke_opt = match self.sgc(invoke_level) {
                 Some(invoke_level) => Some(invoke_level),
                 None => self.sgt(),
             }
         }
+        // The last step might have grown the chain past the limits
+        self.limits_exceeded();
     }
 
     /// Generate a permutation that is with high probably a schrier generator for the current subgroup.
@@ -183,19 +208,19 @@ where
         // First create coset_representative * t random coset reprensentatives.
         //Precompute all coset representatives if we'll be selecting more than there are in the orbit
         let mut gens: Vec<WordPermutation<P>> = {
-            let cache = &mut *record.representative_cache.borrow_mut();
+            let cache = &mut *record.representative_cache();
             if coset_representatives * t >= record.transversal.len() {
                 record
                     .transversal
                     .keys()
                     .map(|point| {
                         cache
-                            .entry(*point)
+                            .entry(point.clone())
                             .or_insert_with(|| {
                                 representative_raw_as_word(
                                     &record.transversal,
-                                    record.base,
-                                    *point,
+                                    record.base.clone(),
+                                    point.clone(),
                                     &self.action,
                                     self.max_depths[level],
                                 )
@@ -210,14 +235,14 @@ where
                     .keys()
                     .choose_multiple(&mut *self.rng.borrow_mut(), coset_representatives * t)
                     .iter()
-                    .map(|&&point| {
+                    .map(|&point| {
                         cache
-                            .entry(point)
+                            .entry(point.clone())
                             .or_insert_with(|| {
                                 representative_raw_as_word(
                                     &record.transversal,
-                                    record.base,
-                                    point,
+                                    record.base.clone(),
+                                    point.clone(),
                                     &self.action,
                                     self.max_depths[level],
                                 )
@@ -254,13 +279,14 @@ where
     /// Check if adding a new element modifies the current layer of the chain.
     fn check_transversal_augmentation(&mut self, p: &P, level: usize, check_transversal: bool) {
         debug!(level = level, perm = %p, "Checking transversal augmentation with perm");
+        let action = &self.action;
         let record = &mut self.chain[level];
         // We optionally check if this element alters the transversal
         if check_transversal
             && record
                 .transversal
                 .keys()
-                .all(|x| record.transversal.contains_key(&p.apply(*x)))
+                .all(|x| record.transversal.contains_key(&action.apply(p, x.clone())))
         {
             // If there are any new orbit elements found, then we add this generator to this level.
             return;
@@ -271,10 +297,12 @@ where
         let mut recompute_transversal = false;
         // First partion points at maximum depth and those not.
         let max_depth = self.max_depths[level];
-        let (max_depth_points, mut to_check): (VecDeque<usize>, VecDeque<usize>) =
-            self.depths[level].keys().cloned().partition(|&x| x == max_depth);
+        let (max_depth_points, to_check): (VecDeque<_>, VecDeque<_>) =
+            self.depths[level].iter().partition(|(_, &depth)| depth == max_depth);
+        let mut to_check: VecDeque<A::OrbitT> = to_check.into_iter().map(|(x, _)| x.clone()).collect();
         // If any point at maximum depth is augmented then we recompute, as this exceeds the current maximum depth.
-        for x in max_depth_points {
+        for (x, _) in max_depth_points {
+            let x = x.clone();
             let application = self.action.apply(p, x);
             // If we find a point that exceeds the depth, then we need a new shallow transversal.
             if !record.transversal.contains_key(&application) {
@@ -288,9 +316,9 @@ where
             let mut new_depths = DetHashMap::default();
             while let Some(x) = to_check.pop_front() {
                 let current_depth = self.depths[level].get(&x).unwrap();
-                let new_image = self.action.apply(p, x);
+                let new_image = self.action.apply(p, x.clone());
                 if !(record.transversal.contains_key(&new_image) || new_transversal.contains_key(&new_image)) {
-                    new_transversal.insert(new_image, p.inv());
+                    new_transversal.insert(new_image.clone(), p.inv());
                     new_depths.insert(new_image, current_depth + 1);
                 }
             }
@@ -304,16 +332,16 @@ where
                 let orbit_depth = *self.depths[level].get(&orbit_element).unwrap();
                 // For each generator (and p)
                 for generator in record.gens.generators() {
-                    let new_image = self.action.apply(generator, orbit_element);
+                    let new_image = self.action.apply(generator, orbit_element.clone());
                     // If we haven't already seen the image
-                    if let Entry::Vacant(e) = record.transversal.entry(new_image) {
+                    if let Entry::Vacant(e) = record.transversal.entry(new_image.clone()) {
                         // If we've reached the maximum depth then we need to stop and recompute.
                         if orbit_depth == max_depth {
                             recompute_transversal = true;
                             break 'element_checking;
                         } else {
                             e.insert(generator.inv());
-                            self.depths[level].insert(new_image, orbit_depth + 1);
+                            self.depths[level].insert(new_image.clone(), orbit_depth + 1);
                             to_check.push_back(new_image)
                         }
                     }
@@ -322,14 +350,18 @@ where
         }
         //Calculate a new shallow transversal.
         if recompute_transversal {
-            let (transversal, new_depth) =
-                shallow_transversal(&mut record.gens, record.base, &self.action, &mut *self.rng.borrow_mut());
+            let (transversal, new_depth) = shallow_transversal(
+                &mut record.gens,
+                record.base.clone(),
+                &self.action,
+                &mut *self.rng.borrow_mut(),
+            );
             record.transversal = transversal;
             //Update the depths of the current position.
             self.max_depths.push(*new_depth.values().max().unwrap());
             self.depths[level] = new_depth;
             // Clear the cache
-            record.representative_cache.borrow_mut().clear();
+            record.representative_cache().clear();
         }
     }
 
@@ -388,7 +420,7 @@ where
                     }
                 };
                 //We have found a residue that has not sifted through, so we add a new base point with this point as a generator.
-                if !h_residue.id_on_iter(evaluated_points) {
+                if !self.id_on_points(&h_residue, evaluated_points) {
                     //Not all permutations have been discarded
                     all_discarded = false;
                     let h_star = h_residue.evaluate();
@@ -499,6 +531,9 @@ where
         gens.extend(self.original_generators.generators().iter().cloned());
         //Sift the original generators, and products of the form g*w_{1,2}.
         while size != order(self.chain.iter()) {
+            if self.limits_exceeded() {
+                return None;
+            }
             while products.is_empty() {
                 products.extend(self.random_schrier_generators_as_word(
                     0,
@@ -529,11 +564,15 @@ where
     /// Add a new level to the chain, starting with this permutation.
     fn add_new_record(&mut self, gen: P) {
         let moved_point = self.selector.moved_point(&gen, self.base.len());
-        debug!(perm = %gen, moved_point = moved_point, "Extending chain");
+        debug!(perm = %gen, ?moved_point, "Extending chain");
         let mut gens = Group::new(&[gen]);
-        let (transversal, depth) =
-            shallow_transversal(&mut gens, moved_point, &self.action, &mut *self.rng.borrow_mut());
-        let initial_record = StabchainRecord::new(moved_point, gens, transversal);
+        let (transversal, depth) = shallow_transversal(
+            &mut gens,
+            moved_point.clone(),
+            &self.action,
+            &mut *self.rng.borrow_mut(),
+        );
+        let initial_record = StabchainRecord::new(moved_point.clone(), gens, transversal);
         self.base.push(moved_point);
         self.max_depths.push(*depth.values().max().unwrap());
         self.depths.push(depth);
@@ -558,18 +597,17 @@ where
     // Get permutation p_bar such that p.apply(base) == p_bar.apply(base)
     fn bar_func(&self, p: &WordPermutation<P>, level: usize) -> Option<WordPermutation<P>> {
         let record = &self.chain[level];
-        let x = p.apply(record.base);
+        let x = self.action.apply_word(p, record.base.clone());
         if !record.transversal.contains_key(&x) {
             return None;
         }
         let p_bar = record
-            .representative_cache
-            .borrow_mut()
-            .entry(x)
+            .representative_cache()
+            .entry(x.clone())
             .or_insert_with(|| {
                 representative_raw_as_word(
                     &record.transversal,
-                    record.base,
+                    record.base.clone(),
                     x,
                     &self.action,
                     self.max_depths[level],
@@ -577,19 +615,31 @@ where
                 .unwrap()
             })
             .clone();
-        debug_assert!(p.apply(record.base) == p_bar.apply(record.base));
+        debug_assert!(
+            self.action.apply_word(p, record.base.clone()) == self.action.apply_word(&p_bar, record.base.clone())
+        );
         Some(p_bar)
     }
+
+    /// Check that the word acts trivially on the given points
+    fn id_on_points(&self, p: &WordPermutation<P>, points: impl IntoIterator<Item = A::OrbitT>) -> bool {
+        points.into_iter().all(|x| self.action.apply_word(p, x.clone()) == x)
+    }
 }
 
 impl<P, S, A, R> crate::group::stabchain::builder::Builder<P, FactoredTransversalResolver<A>, A>
     for StabchainBuilderRandomSTrees<P, S, A, R>
 where
     P: Permutation,
-    A: Action<P, OrbitT = usize>,
+    A: Action<P>,
+    A::OrbitT: Ord,
     S: BaseSelector<P, A::OrbitT>,
     R: Rng + Clone + Debug,
 {
+    fn set_limits(&mut self, limits: Limits) {
+        self.limits = LimitTracker::new(limits);
+    }
+
     fn set_generators(&mut self, gens: &Group<P>) {
         self.construct_strong_generating_set(gens);
     }
@@ -597,4 +647,9 @@ where
     fn build(self) -> Stabchain<P, FactoredTransversalResolver<A>, A> {
         self.build()
     }
+
+    fn try_build(self) -> Result<Stabchain<P, FactoredTransversalResolver<A>, A>, LimitError> {
+        self.limits.result()?;
+        Ok(self.build())
+    }
 }
@@ -5,8 +5,8 @@ use {
     crate::{
         group::orbit::abstraction::TransversalResolver,
         perm::{
-            impls::word::WordPermutation,
             Action,
+            LazyPerm,
             Permutation,
         },
     },
@@ -91,6 +91,29 @@ where
     }
 }
 
+/// Sift the permutation through the chain, returning the residue that is left once it goes through every layer,
+/// or None if it is not in the orbit of one of the layers
+pub fn sift<'a, P, A, V>(it: impl IntoIterator<Item = &'a StabchainRecord<P, V, A>>, p: &P) -> Option<P>
+where
+    P: 'a + Permutation,
+    A: 'a + Action<P>,
+    V: 'a + TransversalResolver<P, A>,
+{
+    let applicator = A::default();
+
+    let mut g = p.clone();
+    for record in it {
+        let base = record.base.clone();
+        let application = applicator.apply(&g, base.clone());
+        let representative = record
+            .resolver()
+            .representative(&record.transversal, base, application)?;
+        g = g.divide(&representative);
+    }
+
+    Some(g)
+}
+
 /// Sift the permutation through the chain, returning the residue it generates.
 pub fn residue_as_words<'a, P, A, V>(it: impl IntoIterator<Item = &'a StabchainRecord<P, V, A>>, p: &P) -> Vec<P>
 where
@@ -126,15 +149,57 @@ where
 /// Sift the permutation word through the chain, returning the residue it generates and the drop out level.
 pub fn residue_as_words_from_words<'a, V, A, P>(
     it: impl IntoIterator<Item = &'a StabchainRecord<P, V, A>>,
-    p: &WordPermutation<P>,
-) -> (usize, WordPermutation<P>)
+    p: &LazyPerm<P>,
+) -> (usize, LazyPerm<P>)
+where
+    V: 'a + TransversalResolver<P, A>,
+    P: 'a + Permutation,
+    A: 'a + Action<P>,
+{
+    let (k, g, _) = sift_word(it, p.clone());
+    (k, g)
+}
+
+/// Sift a lazy product through the chain, returning the residue as a lazy product,
+/// or None if it is not in the orbit of one of the layers. Neither the product nor the residue is evaluated.
+pub fn sift_lazy<'a, P, A, V>(
+    it: impl IntoIterator<Item = &'a StabchainRecord<P, V, A>>,
+    p: &LazyPerm<P>,
+) -> Option<LazyPerm<P>>
+where
+    V: 'a + TransversalResolver<P, A>,
+    P: 'a + Permutation,
+    A: 'a + Action<P>,
+{
+    match sift_word(it, p.clone()) {
+        (_, g, true) => Some(g),
+        (_, _, false) => None,
+    }
+}
+
+/// Same as is_in_group, for a lazy product. Only the residue is evaluated, to check it is the identity
+pub fn is_in_group_lazy<'a, P, A, V>(
+    it: impl IntoIterator<Item = &'a StabchainRecord<P, V, A>>,
+    p: &LazyPerm<P>,
+) -> bool
+where
+    V: 'a + TransversalResolver<P, A>,
+    P: 'a + Permutation,
+    A: 'a + Action<P>,
+{
+    sift_lazy(it, p).is_some_and(|g| g.is_id())
+}
+
+// Sift the word through the chain, returning the drop out level, the residue and whether it sifted through every layer
+fn sift_word<'a, V, A, P>(
+    it: impl IntoIterator<Item = &'a StabchainRecord<P, V, A>>,
+    mut g: LazyPerm<P>,
+) -> (usize, LazyPerm<P>, bool)
 where
     V: 'a + TransversalResolver<P, A>,
     P: 'a + Permutation,
     A: 'a + Action<P>,
 {
-    //This permutation word will store the resulting residue.
-    let mut g: WordPermutation<P> = p.clone();
     //This counts how many layers of the chain the permutation sifts through.
     let mut k = 0;
     let applicator = A::default();
@@ -144,11 +209,11 @@ where
 
         //There is a missing point, so this permutation has not sifted through.
         if !record.transversal.contains_key(&application) {
-            break;
+            return (k, g, false);
         }
         let transversal = &record.transversal;
         //Already check the point is present, so there should be a representative.
-        let cache = &mut *record.representative_cache.borrow_mut();
+        let cache = &mut *record.representative_cache();
         let representative = cache.entry(application.clone()).or_insert_with(|| {
             V::default()
                 .representative_as_word(transversal, base.clone(), application)
@@ -157,7 +222,7 @@ where
         g.multiply_mut_word(&representative.inv_lazy());
         k += 1;
     }
-    (k, g)
+    (k, g, true)
 }
 
 #[cfg(test)]
@@ -327,4 +392,27 @@ mod tests {
         }
         assert_eq!(acc, perm);
     }
+
+    #[test]
+    fn lazy_sifting() {
+        use crate::perm::{
+            builder::{
+                join::MultiJoin,
+                PermBuilder,
+            },
+            utils::random_permutation,
+        };
+
+        let g = Group::alternating(7);
+        let stab = g.stabchain();
+        for _ in 0..20 {
+            let (a, b): (DefaultPermutation, _) = (random_permutation(7), random_permutation(7));
+            let product = a.build_multiply(&b).build_divide(&a).lazy();
+            let residue = sift_lazy(stab.iter(), &product);
+            assert_eq!(residue.is_some(), sift(stab.iter(), &product.evaluate()).is_some());
+            assert_eq!(stab.in_group_lazy(&product), stab.in_group(&b));
+            let square: MultiJoin<_> = vec![a.clone(), a].into_iter().collect();
+            assert!(stab.in_group_lazy(&square.lazy()));
+        }
+    }
 }
@@ -0,0 +1,381 @@
+//! Serde support for stabilizer chains.
+//!
+//! Chains are exported layer by layer, with the base point, the generators of the layer and the transversal map,
+//! with all permutations stored as `ExportablePermutation`. The kind of transversal is stored as well, since
+//! the maps of factored transversals only store the labels of the Schreier tree.
+//! Loading a chain checks that it is valid and complete: the generators of every layer fix the earlier base points,
+//! and the Schreier generators of every layer sift through the layers below, so that a truncated file is rejected
+//! rather than giving a wrong order.
+
+use {
+    super::{
+        complete_stabchain,
+        valid_stabchain,
+        Stabchain,
+        StabchainError,
+        StabchainRecord,
+    },
+    crate::{
+        group::{
+            orbit::abstraction::{
+                TransversalKind,
+                TransversalResolver,
+            },
+            Group,
+        },
+        perm::{
+            export::ExportablePermutation,
+            Action,
+            Permutation,
+        },
+    },
+    serde::{
+        de::Error,
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    },
+    std::convert::TryFrom,
+};
+
+/// A layer of a stabilizer chain in a form that is easy to export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportableStabchainRecord<T = usize> {
+    base: T,
+    generators: Vec<ExportablePermutation>,
+    // A list of pairs rather than a map, as most formats only allow strings as keys
+    transversal: Vec<(T, ExportablePermutation)>,
+}
+
+/// A stabilizer chain in a form that is easy to export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportableStabchain<T = usize> {
+    transversal: TransversalKind,
+    chain: Vec<ExportableStabchainRecord<T>>,
+}
+
+impl<T> ExportableStabchain<T> {
+    /// The kind of transversal the chain was built with
+    pub fn transversal_kind(&self) -> TransversalKind {
+        self.transversal
+    }
+
+    /// Get an iterator over the exported records
+    pub fn iter(&self) -> impl Iterator<Item = &ExportableStabchainRecord<T>> {
+        self.chain.iter()
+    }
+}
+
+impl<P, V, A> From<&StabchainRecord<P, V, A>> for ExportableStabchainRecord<A::OrbitT>
+where
+    P: Permutation,
+    A: Action<P>,
+{
+    fn from(record: &StabchainRecord<P, V, A>) -> Self {
+        Self {
+            base: record.base.clone(),
+            generators: record.gens.generators().iter().cloned().map(Into::into).collect(),
+            transversal: record
+                .transversal
+                .iter()
+                .map(|(point, p)| (point.clone(), p.clone().into()))
+                .collect(),
+        }
+    }
+}
+
+impl<P, V, A> TryFrom<ExportableStabchainRecord<A::OrbitT>> for StabchainRecord<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    V: TransversalResolver<P, A>,
+{
+    type Error = crate::Error;
+
+    fn try_from(record: ExportableStabchainRecord<A::OrbitT>) -> Result<Self, Self::Error> {
+        Ok(StabchainRecord::new(
+            record.base,
+            record
+                .generators
+                .into_iter()
+                .map(|p| p.try_into_perm())
+                .collect::<Result<Group<P>, _>>()?,
+            record
+                .transversal
+                .into_iter()
+                .map(|(point, p)| Ok((point, p.try_into_perm()?)))
+                .collect::<Result<_, crate::Error>>()?,
+        ))
+    }
+}
+
+impl<P, V, A> From<&Stabchain<P, V, A>> for ExportableStabchain<A::OrbitT>
+where
+    P: Permutation,
+    A: Action<P>,
+    V: TransversalResolver<P, A>,
+{
+    fn from(chain: &Stabchain<P, V, A>) -> Self {
+        Self {
+            transversal: V::default().kind(),
+            chain: chain.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<P, V, A> TryFrom<ExportableStabchain<A::OrbitT>> for Stabchain<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    V: TransversalResolver<P, A>,
+{
+    type Error = StabchainError<P, A::OrbitT>;
+
+    fn try_from(exported: ExportableStabchain<A::OrbitT>) -> Result<Self, Self::Error> {
+        let expected = V::default().kind();
+        if exported.transversal != expected {
+            return Err(StabchainError::IncompatibleTransversal(exported.transversal, expected));
+        }
+
+        let chain = Stabchain {
+            chain: exported
+                .chain
+                .into_iter()
+                .map(StabchainRecord::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(StabchainError::InvalidPermutation)?,
+        };
+        valid_stabchain(&chain)?;
+        complete_stabchain(&chain)?;
+        Ok(chain)
+    }
+}
+
+impl<P, V, A> Serialize for StabchainRecord<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    A::OrbitT: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExportableStabchainRecord::from(self).serialize(serializer)
+    }
+}
+
+/// Note that a single record cannot be validated, see the implementation for `Stabchain` instead.
+impl<'de, P, V, A> Deserialize<'de> for StabchainRecord<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    A::OrbitT: Deserialize<'de>,
+    V: TransversalResolver<P, A>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let exported = ExportableStabchainRecord::deserialize(deserializer)?;
+        StabchainRecord::try_from(exported).map_err(D::Error::custom)
+    }
+}
+
+impl<P, V, A> Serialize for Stabchain<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    A::OrbitT: Serialize,
+    V: TransversalResolver<P, A>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExportableStabchain::from(self).serialize(serializer)
+    }
+}
+
+impl<'de, P, V, A> Deserialize<'de> for Stabchain<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    A::OrbitT: Deserialize<'de>,
+    V: TransversalResolver<P, A>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let exported = ExportableStabchain::deserialize(deserializer)?;
+        Stabchain::try_from(exported).map_err(|e| D::Error::custom(format!("invalid stabilizer chain: {:?}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            group::{
+                orbit::abstraction::{
+                    FactoredTransversalResolver,
+                    SimpleTransversalResolver,
+                },
+                stabchain::{
+                    base::{
+                        selectors::{
+                            adaptors::LiftedSelector,
+                            LmpSelector,
+                        },
+                        Base,
+                    },
+                    builder::{
+                        IftBuilderStrategy,
+                        NaiveBuilderStrategy,
+                    },
+                },
+            },
+            perm::{
+                actions::{
+                    OnPairs,
+                    SimpleApplication,
+                },
+                utils::random_permutation,
+                DefaultPermutation,
+            },
+        },
+    };
+
+    type NaiveChain = Stabchain<DefaultPermutation, SimpleTransversalResolver>;
+    type IftChain = Stabchain<DefaultPermutation, FactoredTransversalResolver<SimpleApplication<DefaultPermutation>>>;
+
+    #[test]
+    fn round_trip_naive() {
+        let g = Group::product(&Group::symmetric(5), &Group::dihedral_2n(4));
+        let chain = g.stabchain_with_strategy(NaiveBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let json = serde_json::to_string(&chain).unwrap();
+        let loaded: NaiveChain = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.order(), chain.order());
+        assert_eq!(loaded.base().base(), chain.base().base());
+
+        for _ in 0..20 {
+            let p = random_permutation::<DefaultPermutation>(9);
+            assert_eq!(loaded.in_group(&p), chain.in_group(&p));
+        }
+    }
+
+    #[test]
+    fn round_trip_ift() {
+        let g = Group::alternating(7);
+        let chain = g.stabchain_with_strategy(IftBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let json = serde_json::to_string(&chain).unwrap();
+        let loaded: IftChain = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.order(), 2520u32.into());
+    }
+
+    #[test]
+    fn round_trip_other_action() {
+        let g = Group::symmetric(5);
+        let chain = g.stabchain_with_strategy(NaiveBuilderStrategy::new(
+            OnPairs::default(),
+            LiftedSelector::new(LmpSelector),
+        ));
+        let json = serde_json::to_string(&chain).unwrap();
+        let loaded: Stabchain<DefaultPermutation, SimpleTransversalResolver, OnPairs<DefaultPermutation>> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.order(), 120u32.into());
+    }
+
+    #[test]
+    fn transversal_kind_mismatch() {
+        let chain = Group::symmetric(4)
+            .stabchain_with_strategy(NaiveBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let json = serde_json::to_string(&chain).unwrap();
+        assert!(serde_json::from_str::<IftChain>(&json).is_err());
+    }
+
+    #[test]
+    fn invalid_chain_rejected() {
+        let chain = Group::symmetric(4)
+            .stabchain_with_strategy(NaiveBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let mut exported = ExportableStabchain::from(&chain);
+        exported.chain[0].transversal.pop();
+        assert!(NaiveChain::try_from(exported.clone()).is_err());
+
+        let json = serde_json::to_string(&exported).unwrap();
+        assert!(serde_json::from_str::<NaiveChain>(&json).is_err());
+    }
+
+    #[test]
+    fn truncated_chain_rejected() {
+        let chain = Group::symmetric(5)
+            .stabchain_with_strategy(NaiveBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let mut exported = ExportableStabchain::from(&chain);
+        exported.chain.pop();
+        assert!(matches!(
+            NaiveChain::try_from(exported.clone()),
+            Err(StabchainError::IncompleteLayer(_))
+        ));
+
+        let json = serde_json::to_string(&exported).unwrap();
+        assert!(serde_json::from_str::<NaiveChain>(&json).is_err());
+    }
+
+    // An exported IFT chain of S5, where the labels of the points in the first layer are replaced.
+    // The base point of that layer is 1
+    fn corrupted_labels(points: &[usize], label: &[usize]) -> ExportableStabchain {
+        let chain = Group::symmetric(5)
+            .stabchain_with_strategy(IftBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let mut exported = ExportableStabchain::from(&chain);
+        assert_eq!(exported.chain[0].base, 1);
+        let label = ExportablePermutation::from(DefaultPermutation::from_images(label));
+        for (point, p) in exported.chain[0].transversal.iter_mut() {
+            if points.contains(point) {
+                *p = label.clone();
+            }
+        }
+        exported
+    }
+
+    #[test]
+    fn cyclic_labels_rejected() {
+        // Following the labels from 2 or 3 swaps them forever, and never reaches the base
+        let exported = corrupted_labels(&[2, 3], &[0, 1, 3, 2]);
+        assert!(matches!(
+            IftChain::try_from(exported.clone()),
+            Err(StabchainError::TransversalError(_))
+        ));
+
+        let json = serde_json::to_string(&exported).unwrap();
+        assert!(serde_json::from_str::<IftChain>(&json).is_err());
+    }
+
+    #[test]
+    fn escaping_labels_rejected() {
+        // The label of 2 leads to 5, which is not in the orbit
+        let exported = corrupted_labels(&[2], &[0, 1, 5, 3, 4, 2]);
+        assert!(matches!(
+            IftChain::try_from(exported.clone()),
+            Err(StabchainError::TransversalError(_))
+        ));
+
+        let json = serde_json::to_string(&exported).unwrap();
+        assert!(serde_json::from_str::<IftChain>(&json).is_err());
+    }
+
+    #[test]
+    fn invalid_permutation_rejected() {
+        let json = r#"{"transversal":"Simple","chain":[{"base":0,"generators":[[2,0]],"transversal":[[0,[1]]]}]}"#;
+        let err = NaiveChain::try_from(serde_json::from_str::<ExportableStabchain>(json).unwrap()).unwrap_err();
+        assert!(matches!(
+            err,
+            StabchainError::InvalidPermutation(crate::Error::ZeroPoint)
+        ));
+        assert!(serde_json::from_str::<NaiveChain>(json).is_err());
+
+        let record = r#"{"base":0,"generators":[[2,2]],"transversal":[]}"#;
+        assert!(
+            serde_json::from_str::<StabchainRecord<DefaultPermutation, SimpleTransversalResolver>>(record).is_err()
+        );
+    }
+
+    #[test]
+    fn base_round_trip() {
+        let base: Base = Base::new(vec![3, 1, 4]);
+        let json = serde_json::to_string(&base).unwrap();
+        assert_eq!(json, "{\"points\":[3,1,4]}");
+        let loaded: Base = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.base(), base.base());
+    }
+}
@@ -0,0 +1,286 @@
+//! A read-only stabilizer chain, which can be shared between threads.
+//!
+//! The records of a `Stabchain` keep a cache of the coset representatives computed while sifting, which sits
+//! behind a `RefCell` (or a `Mutex` with the `parallel` feature), and so either prevents the chain from being shared
+//! or makes concurrent queries contend on the lock. Freezing a chain drops the cache,
+//! so that with thread safe permutations (i.e. `SyncPermutation`) it can be wrapped in an `Arc`
+//! and queried concurrently.
+
+use {
+    super::{
+        base::Base,
+        Stabchain,
+        StabchainRecord,
+    },
+    crate::{
+        group::{
+            orbit::abstraction::TransversalResolver,
+            Group,
+        },
+        perm::{
+            actions::SimpleApplication,
+            Action,
+            Permutation,
+        },
+        DetHashMap,
+    },
+    num::BigUint,
+};
+
+/// A stabilizer chain that can no longer be modified. See `Stabchain::freeze`.
+#[derive(Debug, Clone)]
+pub struct FrozenStabchain<P, V, A = SimpleApplication<P>>
+where
+    A: Action<P>,
+    P: Permutation,
+{
+    chain: Vec<FrozenRecord<P, V, A>>,
+}
+
+/// A layer of a frozen stabilizer chain
+#[derive(Debug, Clone)]
+pub struct FrozenRecord<P, V, A = SimpleApplication<P>>
+where
+    A: Action<P>,
+    P: Permutation,
+{
+    base: A::OrbitT,
+    gens: Group<P>,
+    transversal: DetHashMap<A::OrbitT, P>,
+    resolver: V,
+}
+
+impl<P, V, A> FrozenRecord<P, V, A>
+where
+    A: Action<P>,
+    P: Permutation,
+{
+    /// Get the associated group
+    pub fn group(&self) -> &Group<P> {
+        &self.gens
+    }
+
+    /// Get the base of this layer, i.e. the element that the next layer stabilizes
+    pub fn base(&self) -> &A::OrbitT {
+        &self.base
+    }
+
+    /// Get the size of the orbit of the base point
+    pub fn orbit_len(&self) -> usize {
+        self.transversal.len()
+    }
+}
+
+impl<P, V, A> Stabchain<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    V: TransversalResolver<P, A>,
+{
+    /// Freeze the chain, so that it can be shared between threads
+    pub fn freeze(self) -> FrozenStabchain<P, V, A> {
+        FrozenStabchain {
+            chain: self
+                .chain
+                .into_iter()
+                .map(|record| FrozenRecord {
+                    base: record.base,
+                    gens: record.gens,
+                    transversal: record.transversal,
+                    resolver: record.resolver,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<P, V, A> FrozenStabchain<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    V: TransversalResolver<P, A>,
+{
+    /// Turn the chain back into a regular stabilizer chain
+    pub fn thaw(self) -> Stabchain<P, V, A> {
+        Stabchain {
+            chain: self
+                .chain
+                .into_iter()
+                .map(|record| {
+                    let mut thawed = StabchainRecord::new(record.base, record.gens, record.transversal);
+                    thawed.resolver = record.resolver;
+                    thawed
+                })
+                .collect(),
+        }
+    }
+
+    /// Is the element in the group?
+    pub fn in_group(&self, g: &P) -> bool {
+        self.in_subgroup(g, 0)
+    }
+
+    /// Check membership at the subgroup
+    pub fn in_subgroup(&self, g: &P, layer: usize) -> bool {
+        self.sift(g, layer, |_| ())
+    }
+
+    /// Get representatives that multiply to g, so that g == s_m ... s_1
+    pub fn coset_representatives(&self, g: &P) -> Option<Vec<P>> {
+        self.coset_representatives_in_subgroup(g, 0)
+    }
+
+    /// Get representatives that multiply to g
+    pub fn coset_representatives_in_subgroup(&self, g: &P, layer: usize) -> Option<Vec<P>> {
+        let mut representatives = Vec::with_capacity(self.chain.len().saturating_sub(layer));
+        if self.sift(g, layer, |representative| representatives.push(representative.clone())) {
+            Some(representatives)
+        } else {
+            None
+        }
+    }
+
+    /// Calculate the order of the group this stabilizer chain represents.
+    pub fn order(&self) -> BigUint {
+        self.order_subgroup(0)
+    }
+
+    /// Calculate the order of the subgroup this stabilizer chain represents.
+    pub fn order_subgroup(&self, layer: usize) -> BigUint {
+        self.chain
+            .iter()
+            .skip(layer)
+            .map(|record| BigUint::from(record.transversal.len()))
+            .product()
+    }
+
+    /// Get the base corresponding to this stabilizer chain
+    pub fn base(&self) -> Base<P, A> {
+        Base::new_with_action(self.chain.iter().map(|g| &g.base).cloned().collect(), A::default())
+    }
+
+    /// Get chain length
+    pub fn len(&self) -> usize {
+        self.chain.len()
+    }
+
+    /// Is the chain empty (i.e. originary group was trivial)
+    pub fn is_empty(&self) -> bool {
+        self.chain.is_empty()
+    }
+
+    /// Get G^(n)
+    pub fn layer(&self, n: usize) -> Option<&FrozenRecord<P, V, A>> {
+        self.chain.get(n)
+    }
+
+    /// Get an iterator over the records
+    pub fn iter(&self) -> impl Iterator<Item = &FrozenRecord<P, V, A>> {
+        self.chain.iter()
+    }
+
+    /// Sift g through the chain starting at the given layer, passing every representative to the callback.
+    /// Returns whether g is in the group.
+    fn sift(&self, g: &P, layer: usize, mut on_representative: impl FnMut(&P)) -> bool {
+        if g.is_id() {
+            return true;
+        }
+
+        let applicator = A::default();
+        let mut g = g.clone();
+        for record in self.chain.iter().skip(layer) {
+            let application = applicator.apply(&g, record.base.clone());
+            let representative =
+                match record
+                    .resolver
+                    .representative(&record.transversal, record.base.clone(), application)
+                {
+                    Some(representative) => representative,
+                    None => return false,
+                };
+            on_representative(&representative);
+            g = g.divide(&representative);
+        }
+
+        g.is_id()
+    }
+}
+
+impl<P, V, A> From<Stabchain<P, V, A>> for FrozenStabchain<P, V, A>
+where
+    P: Permutation,
+    A: Action<P>,
+    V: TransversalResolver<P, A>,
+{
+    fn from(chain: Stabchain<P, V, A>) -> Self {
+        chain.freeze()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            group::stabchain::{
+                base::selectors::LmpSelector,
+                builder::IftBuilderStrategy,
+            },
+            perm::{
+                impls::sync::SyncPermutation,
+                utils::random_permutation,
+                DefaultPermutation,
+            },
+        },
+        rayon::prelude::*,
+        std::sync::Arc,
+    };
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    #[test]
+    fn frozen_agrees_with_chain() {
+        let g = Group::product(&Group::symmetric(5), &Group::cyclic(4));
+        let chain = g.stabchain();
+        let frozen = g.stabchain().freeze();
+        assert_eq!(frozen.order(), chain.order());
+        assert_eq!(frozen.len(), chain.len());
+        assert!(frozen.iter().map(|r| r.base()).eq(chain.iter().map(|r| r.base())));
+
+        for _ in 0..50 {
+            let p = random_permutation::<DefaultPermutation>(9);
+            assert_eq!(frozen.in_group(&p), chain.in_group(&p));
+            assert_eq!(frozen.coset_representatives(&p), chain.coset_representatives(&p));
+        }
+    }
+
+    #[test]
+    fn thaw_round_trip() {
+        let g = Group::alternating(6);
+        let chain = g
+            .stabchain_with_strategy(IftBuilderStrategy::new(SimpleApplication::default(), LmpSelector))
+            .freeze()
+            .thaw();
+        crate::group::stabchain::valid_stabchain(&chain).unwrap();
+        assert_eq!(chain.order(), BigUint::from(360u32));
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        let g = Group::symmetric(8).map(SyncPermutation::from);
+        let frozen = Arc::new(g.stabchain().freeze());
+        assert_send_sync(&frozen);
+
+        let h = Group::alternating(8).map(SyncPermutation::from);
+        let mut rng = h.rng();
+        let elements: Vec<_> = (0..100).map(|_| rng.random_permutation()).collect();
+        let results: Vec<_> = elements
+            .par_iter()
+            .map({
+                let frozen = Arc::clone(&frozen);
+                move |p| frozen.in_group(p) && frozen.coset_representatives(p).is_some()
+            })
+            .collect();
+        assert!(results.into_iter().all(|in_group| in_group));
+    }
+}
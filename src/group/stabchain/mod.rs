@@ -4,6 +4,8 @@ pub mod base;
 pub mod base_change_builder;
 pub mod builder;
 pub mod element_testing;
+pub mod export;
+pub mod frozen;
 
 use {
     crate::{
@@ -13,7 +15,11 @@ use {
         },
         perm::{
             actions::SimpleApplication,
-            impls::word::WordPermutation,
+            impls::{
+                slp::SlpPermutation,
+                word::WordPermutation,
+            },
+            slp::Slp,
             *,
         },
     },
@@ -25,6 +31,8 @@ use {
     builder::{
         Builder,
         BuilderStrategy,
+        LimitError,
+        Limits,
     },
 };
 
@@ -65,6 +73,28 @@ where
         builder.build()
     }
 
+    /// Creates a stabilizer chain using a selected strategy, giving up as soon as one of the limits is exceeded.
+    #[tracing::instrument(skip(g))]
+    pub fn try_new_with_strategy<S, B: Builder<P, V, A>>(
+        g: &Group<P>,
+        build_strategy: S,
+        limits: Limits,
+    ) -> Result<Self, LimitError>
+    where
+        S: BuilderStrategy<P, Action = A, Transversal = V, BuilderT = B>,
+    {
+        debug!(G = %g, strat = ?build_strategy, ?limits, "Starting limited Stabilizer chain construction");
+        let mut builder = build_strategy.make_builder();
+        builder.set_limits(limits);
+        builder.set_generators(g);
+        builder.try_build()
+    }
+
+    /// Assemble a chain from its records, without any validation
+    pub(crate) fn from_records(chain: Vec<StabchainRecord<P, V, A>>) -> Self {
+        Self { chain }
+    }
+
     // Utility to get the chain
     fn get_chain_at_layer(&self, n: usize) -> impl Iterator<Item = &StabchainRecord<P, V, A>> {
         self.chain.iter().skip(n)
@@ -80,6 +110,11 @@ where
         element_testing::is_in_group(self.get_chain_at_layer(layer), g)
     }
 
+    /// Is the lazy product in the group? It is sifted without evaluating any intermediate product
+    pub fn in_group_lazy(&self, g: &LazyPerm<P>) -> bool {
+        element_testing::is_in_group_lazy(self.iter(), g)
+    }
+
     /// Get representatives that multiply to g
     /// TODO: If there is something useful to do with these, make a struct for Vec<Permutation>
     pub fn coset_representatives(&self, g: &P) -> Option<Vec<P>> {
@@ -202,6 +237,35 @@ where
     }
 }
 
+impl<P, V, A> Stabchain<SlpPermutation<P>, V, A>
+where
+    P: Permutation,
+    A: Action<SlpPermutation<P>>,
+    V: TransversalResolver<SlpPermutation<P>, A>,
+{
+    /// Express an element of the group as a straight-line program in the generators the chain was built from.
+    /// Returns None if the element is not in the group, or if the chain was built with a strategy that
+    /// loses track of the words (i.e. the random strategies).
+    /// ```
+    /// use stabchain::group::Group;
+    /// use stabchain::perm::utils::random_permutation;
+    /// let g = Group::symmetric(6);
+    /// let chain = g.stabchain_with_words();
+    /// let p = random_permutation(6);
+    /// let slp = chain.factorize(&p).unwrap();
+    /// assert_eq!(slp.evaluate(g.generators()), p);
+    /// ```
+    pub fn factorize(&self, p: &P) -> Option<Slp> {
+        let representatives = self.coset_representatives(&SlpPermutation::untracked(p.clone()))?;
+        // p == s_m ... s_1
+        representatives
+            .iter()
+            .rev()
+            .fold(SlpPermutation::id(), |acc, s| acc.multiply(s))
+            .slp()
+    }
+}
+
 impl<P, V, A> IntoIterator for Stabchain<P, V, A>
 where
     A: Action<P>,
@@ -215,8 +279,15 @@ where
     }
 }
 
+/// Cache of the coset representatives computed while sifting. With the `parallel` feature this is behind a mutex,
+/// so that chains can be shared between threads.
+#[cfg(not(feature = "parallel"))]
+type RepresentativeCache<K, P> = RefCell<DetHashMap<K, WordPermutation<P>>>;
+#[cfg(feature = "parallel")]
+type RepresentativeCache<K, P> = std::sync::Mutex<DetHashMap<K, WordPermutation<P>>>;
+
 /// All the information stored in a layer of the stabilizer chain
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct StabchainRecord<P, V, A = SimpleApplication<P>>
 where
     A: Action<P>,
@@ -226,7 +297,24 @@ where
     gens: Group<P>,
     transversal: DetHashMap<A::OrbitT, P>,
     resolver: V,
-    representative_cache: RefCell<DetHashMap<A::OrbitT, WordPermutation<P>>>,
+    representative_cache: RepresentativeCache<A::OrbitT, P>,
+}
+
+impl<P, V, A> Clone for StabchainRecord<P, V, A>
+where
+    A: Action<P>,
+    P: Permutation,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            gens: self.gens.clone(),
+            transversal: self.transversal.clone(),
+            resolver: self.resolver.clone(),
+            representative_cache: self.representative_cache().clone().into(),
+        }
+    }
 }
 
 impl<P, V, A> StabchainRecord<P, V, A>
@@ -243,6 +331,23 @@ where
     pub fn base(&self) -> &A::OrbitT {
         &self.base
     }
+
+    /// Get exclusive access to the cache of coset representatives
+    #[cfg(not(feature = "parallel"))]
+    pub(crate) fn representative_cache(
+        &self,
+    ) -> impl DerefMut<Target = DetHashMap<A::OrbitT, WordPermutation<P>>> + '_ {
+        self.representative_cache.borrow_mut()
+    }
+
+    /// Get exclusive access to the cache of coset representatives
+    #[cfg(feature = "parallel")]
+    pub(crate) fn representative_cache(
+        &self,
+    ) -> impl DerefMut<Target = DetHashMap<A::OrbitT, WordPermutation<P>>> + '_ {
+        // The cache is only ever filled with valid entries, so a poisoned lock is still usable
+        self.representative_cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 impl<P, V, A> StabchainRecord<P, V, A>
@@ -271,6 +376,11 @@ where
         }
     }
 
+    /// Get the raw transversal map of the record
+    pub(crate) fn transversal_map(&self) -> &DetHashMap<A::OrbitT, P> {
+        &self.transversal
+    }
+
     /// Get the resolver of the record
     pub(crate) fn resolver(&self) -> &V {
         &self.resolver
@@ -283,9 +393,11 @@ where
     }
 }
 
+#[cfg(not(feature = "parallel"))]
+use std::cell::RefCell;
 use std::{
-    cell::RefCell,
     fmt,
+    ops::DerefMut,
 };
 
 impl<P, V, A> fmt::Display for Stabchain<P, V, A>
@@ -316,7 +428,10 @@ where
 
 use {
     super::orbit::{
-        abstraction::FactoredTransversalResolver,
+        abstraction::{
+            FactoredTransversalResolver,
+            TransversalKind,
+        },
         transversal::factored_transversal::factored_transversal_complete_opt,
     },
     crate::{
@@ -331,6 +446,12 @@ pub enum StabchainError<P, OrbitT> {
     TransversalError(TransversalError<P, OrbitT>),
     BasePointNotStabilized(OrbitT),
     IncorrectOrder((BigUint, BigUint)),
+    /// An exported chain was built with a transversal of a different kind (found, expected)
+    IncompatibleTransversal(TransversalKind, TransversalKind),
+    /// An exported chain contains images that do not define a permutation
+    InvalidPermutation(crate::Error),
+    /// The layers below this one do not contain the stabilizer of its base point, e.g. because some are missing
+    IncompleteLayer(usize),
 }
 
 pub fn correct_stabchain_order<P, V, A>(
@@ -365,7 +486,7 @@ where
 
     let applicator = A::default();
 
-    let mut previous: Option<A::OrbitT> = None;
+    let mut previous: Vec<A::OrbitT> = Vec::new();
     for record in s.iter() {
         let gens = record.group();
         let transversal = record.transversal();
@@ -379,13 +500,53 @@ where
         valid_transversal(&transversal).map_err(StabchainError::TransversalError)?;
 
         // Check that everything is stabilized correctly
-        if let Some(stabilized) = previous {
-            if gens.orbit_of_action(stabilized.clone(), &applicator).len() != 1 {
-                return Err(StabchainError::BasePointNotStabilized(stabilized));
+        for stabilized in previous.iter() {
+            if gens
+                .generators()
+                .iter()
+                .any(|g| applicator.apply(g, stabilized.clone()) != *stabilized)
+            {
+                return Err(StabchainError::BasePointNotStabilized(stabilized.clone()));
             }
         }
 
-        previous = Some(record.base.clone());
+        previous.push(record.base.clone());
+    }
+
+    Ok(())
+}
+
+/// Check that a valid chain is complete, i.e. that it has all the layers needed to compute the order of the group,
+/// by sifting the Schreier generators of each layer through the layers below.
+/// The chains of the random builders can fail this with a small probability, unless they were given the order
+pub fn complete_stabchain<P, V, A>(s: &Stabchain<P, V, A>) -> Result<(), StabchainError<P, A::OrbitT>>
+where
+    P: Permutation,
+    V: TransversalResolver<P, A>,
+    A: Action<P>,
+    A::OrbitT: std::fmt::Debug,
+{
+    use crate::group::orbit::transversal::Transversal;
+
+    let applicator = A::default();
+
+    // The chain is valid, so that every point of an orbit has a representative
+    for (i, record) in s.iter().enumerate() {
+        let transversal = record.transversal();
+        let representatives: DetHashMap<_, _> = transversal
+            .orbit()
+            .iter()
+            .map(|point| (point.clone(), transversal.representative(point.clone()).unwrap()))
+            .collect();
+        for (point, representative) in representatives.iter() {
+            for g in record.group().generators() {
+                let image = applicator.apply(g, point.clone());
+                let schreier = representative.multiply(g).divide(&representatives[&image]);
+                if !element_testing::is_in_group(s.get_chain_at_layer(i + 1), &schreier) {
+                    return Err(StabchainError::IncompleteLayer(i));
+                }
+            }
+        }
     }
 
     Ok(())
@@ -739,6 +900,48 @@ mod tests {
     reconstruction_test!(Group::cyclic(30), reconstruction_cyclic);
     reconstruction_test!(Group::dihedral_2n(10), reconstruction_dihedral);
 
+    #[test]
+    fn factorize_elements() {
+        use crate::perm::utils::random_permutation;
+
+        let g = Group::product(&Group::symmetric(7), &Group::dihedral_2n(5));
+        let chain = g.stabchain_with_words();
+        for _ in 0..20 {
+            let p = random_permutation::<DefaultPermutation>(7).multiply(&g.generators()[2]);
+            let slp = chain.factorize(&p).unwrap();
+            assert_eq!(slp.evaluate(g.generators()), p);
+        }
+        assert!(chain.factorize(&DefaultPermutation::id()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn factorize_non_elements() {
+        use crate::perm::export::CyclePermutation;
+
+        let chain = Group::alternating(6).stabchain_with_words();
+        assert!(chain
+            .factorize(&CyclePermutation::single_cycle(&[1, 2]).into_perm())
+            .is_none());
+    }
+
+    #[test]
+    fn factorize_ift() {
+        use crate::{
+            group::stabchain::{
+                base::selectors::LmpSelector,
+                builder::IftBuilderStrategy,
+            },
+            perm::utils::random_permutation,
+        };
+
+        let g = Group::symmetric(8);
+        let chain = g
+            .slp_generators()
+            .stabchain_with_strategy(IftBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let p = random_permutation::<DefaultPermutation>(8);
+        assert_eq!(chain.factorize(&p).unwrap().evaluate(g.generators()), p);
+    }
+
     stabchain_tests!(
         |_g| NaiveBuilderStrategy::new(
             SimpleApplication::default(),
@@ -829,6 +1032,98 @@ mod tests {
         },
         random_shallow_quick_test
     );
+    stabchain_tests!(
+        |_g| NaiveBuilderStrategy::new(
+            OnTuples::default(),
+            crate::group::stabchain::base::selectors::adaptors::LiftedSelector::new(
+                crate::group::stabchain::base::selectors::LmpSelector
+            )
+        ),
+        naive_on_tuples
+    );
+    stabchain_tests!(
+        |_g| {
+            use {
+                crate::group::stabchain::builder::random::parameters::RandomAlgoParameters,
+                rand::SeedableRng,
+            };
+            RandomBuilderStrategyNaive::new_with_params(
+                OnPairs::default(),
+                crate::group::stabchain::base::selectors::adaptors::LiftedSelector::new(
+                    crate::group::stabchain::base::selectors::FmpSelector,
+                ),
+                RandomAlgoParameters::default().rng(rand_xorshift::XorShiftRng::from_seed([58; 16])),
+            )
+        },
+        random_on_pairs
+    );
+    stabchain_tests!(
+        |_g| {
+            use {
+                crate::group::stabchain::builder::random::parameters::RandomAlgoParameters,
+                rand::SeedableRng,
+            };
+            RandomBuilderStrategyShallow::new_with_params(
+                OnSets::default(),
+                crate::group::stabchain::base::selectors::adaptors::LiftedSelector::new(
+                    crate::group::stabchain::base::selectors::FmpSelector,
+                ),
+                RandomAlgoParameters::default().rng(rand_xorshift::XorShiftRng::from_seed([52; 16])),
+            )
+        },
+        random_shallow_on_sets
+    );
+    stabchain_tests!(
+        |g| {
+            use {
+                crate::group::stabchain::builder::random::parameters::RandomAlgoParameters,
+                rand::SeedableRng,
+            };
+            RandomBuilderStrategyShallow::new_with_params(
+                OnTuples::default(),
+                crate::group::stabchain::base::selectors::adaptors::LiftedSelector::new(
+                    crate::group::stabchain::base::selectors::FmpSelector,
+                ),
+                RandomAlgoParameters::default()
+                    .rng(rand_xorshift::XorShiftRng::from_seed([42; 16]))
+                    .order(g),
+            )
+        },
+        random_shallow_on_tuples_known_order
+    );
+
+    #[test]
+    #[allow(deprecated)]
+    fn random_initial_base_point() {
+        use crate::{
+            group::stabchain::{
+                base::selectors::LmpSelector,
+                builder::{
+                    RandomBuilderStrategyNaive,
+                    RandomBuilderStrategyShallow,
+                },
+            },
+            perm::export::CyclePermutation,
+        };
+
+        // The smallest of the points selected for each generator, even when the first generator is the identity
+        let g = Group::from_list(vec![
+            DefaultPermutation::id(),
+            CyclePermutation::single_cycle(&[4, 5]).into(),
+            CyclePermutation::single_cycle(&[1, 2, 3]).into(),
+        ]);
+        let chain = g.stabchain_with_strategy(RandomBuilderStrategyNaive::new(
+            SimpleApplication::default(),
+            LmpSelector,
+        ));
+        assert_eq!(chain.base().base()[0], 2);
+        let chain = g.stabchain_with_strategy(RandomBuilderStrategyShallow::new(
+            SimpleApplication::default(),
+            LmpSelector,
+        ));
+        assert_eq!(chain.base().base()[0], 2);
+        assert_eq!(chain.order(), 6u32.into());
+    }
 
     known_base_tests!(
         RandomBaseChangeStrategy::new(SimpleApplication::default()),
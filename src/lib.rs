@@ -1,6 +1,9 @@
+pub mod error;
 pub mod group;
 pub mod perm;
 
+pub use error::Error;
+
 use std::{
     collections::{
         hash_map::{
@@ -20,7 +20,7 @@ where
         p.apply(input)
     }
 
-    fn apply_word(&self, p: &WordPermutation<P>, input: Self::OrbitT) -> Self::OrbitT
+    fn apply_word(&self, p: &LazyPerm<P>, input: Self::OrbitT) -> Self::OrbitT
     where
         P: Permutation,
     {
@@ -70,6 +70,126 @@ where
     }
 }
 
+/// Orbit elements that can be built from a single point, so that the element is moved by a permutation
+/// iff the point is. Used to select base points for actions on structures over the points.
+pub trait FromPoint {
+    /// Build the element from a point
+    fn from_point(x: usize) -> Self;
+}
+
+impl FromPoint for usize {
+    fn from_point(x: usize) -> Self {
+        x
+    }
+}
+
+impl FromPoint for Vec<usize> {
+    fn from_point(x: usize) -> Self {
+        vec![x]
+    }
+}
+
+impl FromPoint for (usize, usize) {
+    fn from_point(x: usize) -> Self {
+        (x, x)
+    }
+}
+
+impl FromPoint for Vec<Vec<usize>> {
+    fn from_point(x: usize) -> Self {
+        vec![vec![x]]
+    }
+}
+
+/// Action on sets of points, represented as sorted vectors
+#[derive(Debug, Clone)]
+pub struct OnSets<P>(std::marker::PhantomData<P>);
+
+impl<P> Default for OnSets<P> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<P> Action<P> for OnSets<P>
+where
+    P: Permutation,
+{
+    type OrbitT = Vec<usize>;
+
+    fn apply(&self, p: &P, input: Self::OrbitT) -> Self::OrbitT {
+        let mut set: Vec<_> = input.into_iter().map(|x| p.apply(x)).collect();
+        set.sort_unstable();
+        set
+    }
+}
+
+/// Action on tuples of points, applying the permutation to each entry
+#[derive(Debug, Clone)]
+pub struct OnTuples<P>(std::marker::PhantomData<P>);
+
+impl<P> Default for OnTuples<P> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<P> Action<P> for OnTuples<P>
+where
+    P: Permutation,
+{
+    type OrbitT = Vec<usize>;
+
+    fn apply(&self, p: &P, input: Self::OrbitT) -> Self::OrbitT {
+        input.into_iter().map(|x| p.apply(x)).collect()
+    }
+}
+
+/// Action on ordered pairs of points
+#[derive(Debug, Clone)]
+pub struct OnPairs<P>(std::marker::PhantomData<P>);
+
+impl<P> Default for OnPairs<P> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<P> Action<P> for OnPairs<P>
+where
+    P: Permutation,
+{
+    type OrbitT = (usize, usize);
+
+    fn apply(&self, p: &P, input: Self::OrbitT) -> Self::OrbitT {
+        (p.apply(input.0), p.apply(input.1))
+    }
+}
+
+/// Action on sets of sets of points, both represented as sorted vectors
+#[derive(Debug, Clone)]
+pub struct OnSetsOfSets<P>(std::marker::PhantomData<P>);
+
+impl<P> Default for OnSetsOfSets<P> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<P> Action<P> for OnSetsOfSets<P>
+where
+    P: Permutation,
+{
+    type OrbitT = Vec<Vec<usize>>;
+
+    fn apply(&self, p: &P, input: Self::OrbitT) -> Self::OrbitT {
+        let on_sets = OnSets::default();
+        let mut sets: Vec<_> = input.into_iter().map(|set| on_sets.apply(p, set)).collect();
+        sets.sort_unstable();
+        sets
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -125,4 +245,110 @@ mod tests {
         crate::group::Group::symmetric(5).bruteforce_elements(),
         conj
     );
+    action_tests!(
+        OnSets<DefaultPermutation>,
+        vec![vec![], vec![0], vec![1, 5, 7], vec![3, 20, 49]],
+        on_sets
+    );
+    action_tests!(
+        OnTuples<DefaultPermutation>,
+        vec![vec![], vec![0], vec![7, 5, 1], vec![20, 3, 20, 49]],
+        on_tuples
+    );
+    action_tests!(OnPairs<DefaultPermutation>, vec![(0, 0), (1, 5), (49, 3)], on_pairs);
+    action_tests!(
+        OnSetsOfSets<DefaultPermutation>,
+        vec![
+            vec![],
+            vec![vec![0]],
+            vec![vec![1, 5], vec![3, 20, 49]],
+            vec![vec![], vec![7]]
+        ],
+        on_sets_of_sets
+    );
+
+    mod structures {
+        use {
+            super::super::*,
+            crate::group::{
+                orbit::{
+                    transversal::{
+                        valid_transversal,
+                        Transversal,
+                    },
+                    Orbit,
+                },
+                stabchain::{
+                    base::selectors::{
+                        adaptors::LiftedSelector,
+                        LmpSelector,
+                    },
+                    builder::{
+                        IftBuilderStrategy,
+                        NaiveBuilderStrategy,
+                    },
+                    valid_stabchain,
+                },
+                Group,
+            },
+        };
+
+        #[test]
+        fn orbit_sizes() {
+            let g = Group::symmetric(6);
+            assert_eq!(Orbit::new_with_action(&g, vec![0, 1], &OnSets::default()).len(), 15);
+            assert_eq!(Orbit::new_with_action(&g, vec![0, 1], &OnTuples::default()).len(), 30);
+            assert_eq!(Orbit::new_with_action(&g, (0, 1), &OnPairs::default()).len(), 30);
+            assert_eq!(Orbit::new_with_action(&g, (2, 2), &OnPairs::default()).len(), 6);
+            let partition = vec![vec![0, 1], vec![2, 3], vec![4, 5]];
+            assert_eq!(
+                Orbit::new_with_action(&g, partition, &OnSetsOfSets::default()).len(),
+                15
+            );
+        }
+
+        #[test]
+        fn transversals() {
+            let g = Group::alternating(6);
+            valid_transversal(&g.transversal_of_action(vec![0, 2], OnSets::default())).unwrap();
+            valid_transversal(&g.factored_transversal_of_action(vec![0, 2], OnTuples::default())).unwrap();
+            valid_transversal(&g.transversal_of_action((3, 1), OnPairs::default())).unwrap();
+            let partition = vec![vec![0, 1, 2], vec![3, 4, 5]];
+            let transversal = g.factored_transversal_of_action(partition, OnSetsOfSets::default());
+            valid_transversal(&transversal).unwrap();
+            assert_eq!(transversal.orbit().len(), 10);
+        }
+
+        #[test]
+        fn stabchains() {
+            let g = Group::symmetric(7);
+            let chain = g.stabchain_with_strategy(NaiveBuilderStrategy::new(
+                OnSets::default(),
+                LiftedSelector::new(LmpSelector),
+            ));
+            valid_stabchain(&chain).unwrap();
+            assert_eq!(chain.order(), 5040u16.into());
+
+            let chain = g.stabchain_with_strategy(IftBuilderStrategy::new(
+                OnTuples::default(),
+                LiftedSelector::new(LmpSelector),
+            ));
+            valid_stabchain(&chain).unwrap();
+            assert_eq!(chain.order(), 5040u16.into());
+
+            let chain = g.stabchain_with_strategy(NaiveBuilderStrategy::new(
+                OnPairs::default(),
+                LiftedSelector::new(LmpSelector),
+            ));
+            valid_stabchain(&chain).unwrap();
+            assert_eq!(chain.order(), 5040u16.into());
+
+            let chain = g.stabchain_with_strategy(IftBuilderStrategy::new(
+                OnSetsOfSets::default(),
+                LiftedSelector::new(LmpSelector),
+            ));
+            valid_stabchain(&chain).unwrap();
+            assert_eq!(chain.order(), 5040u16.into());
+        }
+    }
 }
@@ -3,6 +3,8 @@ use {
     crate::perm::Permutation,
 };
 
+use num::integer::lcm;
+
 /// The implementation of inverse, to be used mostly for benchmarking
 pub fn inv(p: &StandardPermutation) -> StandardPermutation {
     let vals = p.as_vec();
@@ -10,6 +12,13 @@ pub fn inv(p: &StandardPermutation) -> StandardPermutation {
     StandardPermutation::from_vec_unchecked(inv)
 }
 
+#[cfg(feature = "simd")]
+pub(super) fn inv_unchecked(vals: &[usize]) -> Vec<usize> {
+    // Safety: the images are those of a permutation
+    unsafe { super::simd::invert(vals) }
+}
+
+#[cfg(not(feature = "simd"))]
 pub(super) fn inv_unchecked(vals: &[usize]) -> Vec<usize> {
     let mut v = vec![0; vals.len()];
     for i in 0..vals.len() {
@@ -35,3 +44,99 @@ pub fn order_cycle(p: &impl Permutation) -> usize {
     let cycle = CyclePermutation::from_images(&images[..]);
     cycle.order()
 }
+
+/// The lengths of the non-trivial cycles of the images, in the order their smallest points appear
+pub(super) fn cycle_lengths(vals: &[usize]) -> Vec<usize> {
+    let mut seen = vec![false; vals.len()];
+    let mut lengths = Vec::new();
+    for start in 0..vals.len() {
+        let mut current = start;
+        let mut len = 0;
+        while !seen[current] {
+            seen[current] = true;
+            current = vals[current];
+            len += 1;
+        }
+        if len > 1 {
+            lengths.push(len);
+        }
+    }
+    lengths
+}
+
+/// The lengths of the non-trivial cycles of the images, in non-increasing order
+pub(super) fn cycle_type(vals: &[usize]) -> Vec<usize> {
+    let mut lengths = cycle_lengths(vals);
+    lengths.sort_unstable_by(|a, b| b.cmp(a));
+    lengths
+}
+
+/// 0 if the images define an even permutation, 1 otherwise
+pub(super) fn parity(vals: &[usize]) -> usize {
+    cycle_lengths(vals).into_iter().map(|len| len - 1).sum::<usize>() % 2
+}
+
+/// The order, as the lcm of the cycle lengths
+pub(super) fn order(vals: &[usize]) -> usize {
+    cycle_lengths(vals).into_iter().fold(1, lcm)
+}
+
+/// The points moved by the images
+pub(super) fn support(vals: &[usize]) -> Vec<usize> {
+    (0..vals.len()).filter(|&i| vals[i] != i).collect()
+}
+
+/// The points fixed by the images
+pub(super) fn fixed_points(vals: &[usize]) -> Vec<usize> {
+    (0..vals.len()).filter(|&i| vals[i] == i).collect()
+}
+
+/// Iterator over the non-trivial cycles of a permutation, see [Permutation::cycles].
+/// It only visits the support, so that it scales with the number of moved points rather than the degree.
+#[derive(Debug)]
+pub struct Cycles<'a, P> {
+    perm: &'a P,
+    support: Vec<usize>,
+    seen: Vec<bool>,
+    next: usize,
+}
+
+impl<'a, P: Permutation> Cycles<'a, P> {
+    pub(super) fn new(perm: &'a P) -> Self {
+        let support = perm.support();
+        Self {
+            perm,
+            seen: vec![false; support.len()],
+            support,
+            next: 0,
+        }
+    }
+}
+
+impl<P: Permutation> Iterator for Cycles<'_, P> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.support.len() {
+            let start = self.next;
+            self.next += 1;
+            if self.seen[start] {
+                continue;
+            }
+
+            let mut cycle = Vec::new();
+            let mut current = self.support[start];
+            // The support is sorted, and closed under the permutation
+            while let Ok(index) = self.support.binary_search(&current) {
+                if self.seen[index] {
+                    break;
+                }
+                self.seen[index] = true;
+                cycle.push(current);
+                current = self.perm.apply(current);
+            }
+            return Some(cycle);
+        }
+        None
+    }
+}
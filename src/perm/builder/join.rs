@@ -1,5 +1,8 @@
 use {
-    super::PermBuilder,
+    super::{
+        LazyPerm,
+        PermBuilder,
+    },
     crate::perm::Permutation,
     std::iter::FromIterator,
 };
@@ -32,6 +35,12 @@ where
 
         first.multiply(&second)
     }
+
+    fn lazy(&self) -> LazyPerm<P> {
+        let mut word = self.first.lazy();
+        word.multiply_mut_word(&self.second.lazy());
+        word
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -64,6 +73,10 @@ impl<P: Clone + Permutation> PermBuilder<P> for MultiJoin<P> {
 
         res
     }
+
+    fn lazy(&self) -> LazyPerm<P> {
+        self.args.iter().cloned().collect()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,548 @@
+use {
+    crate::perm::*,
+    std::iter::{
+        self,
+        FromIterator,
+    },
+};
+
+/// How a [LazyPerm] simplifies itself as letters are appended to it.
+/// By default words are freely reduced and repeated letters are merged, but never collapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordPolicy {
+    free_reduction: bool,
+    merge_powers: bool,
+    collapse_threshold: Option<usize>,
+}
+
+impl WordPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep every letter as it is appended, without any simplification
+    pub fn keep_all() -> Self {
+        Self {
+            free_reduction: false,
+            merge_powers: false,
+            collapse_threshold: None,
+        }
+    }
+
+    /// Cancel a letter against its inverse, so that p p^-1 leaves the word
+    pub fn free_reduction(mut self, free_reduction: bool) -> Self {
+        self.free_reduction = free_reduction;
+        self
+    }
+
+    /// Merge repeated letters, so that p p is stored as a single letter p^2. The power is evaluated eagerly
+    pub fn merge_powers(mut self, merge_powers: bool) -> Self {
+        self.merge_powers = merge_powers;
+        self
+    }
+
+    /// Evaluate the word into a single letter whenever it grows longer than the threshold
+    pub fn collapse_threshold(mut self, threshold: usize) -> Self {
+        self.collapse_threshold = Some(threshold);
+        self
+    }
+}
+
+impl Default for WordPolicy {
+    fn default() -> Self {
+        Self {
+            free_reduction: true,
+            merge_powers: true,
+            collapse_threshold: None,
+        }
+    }
+}
+
+/// A lazy product of permutations, stored as the word of its letters in the order they are applied.
+/// Multiplying words only concatenates them, and points are mapped through every letter in turn, so that
+/// the product is never materialized unless it is evaluated. Any [PermBuilder](super::PermBuilder) expression can be
+/// flattened into one with [PermBuilder::lazy](super::PermBuilder::lazy).
+/// Appended letters are simplified according to the [WordPolicy] of the word.
+#[derive(Debug, Clone, Eq)]
+pub struct LazyPerm<P = DefaultPermutation>
+where
+    P: Permutation,
+{
+    word: Vec<P>,
+    // Every letter is a power of a letter that was appended, which is kept with the exponent for the reductions
+    roots: Vec<(P, usize)>,
+    policy: WordPolicy,
+}
+
+impl<P> LazyPerm<P>
+where
+    P: Permutation,
+{
+    /// Custom initialiser that can take a vector capacity.
+    pub(crate) fn id_with_capacity(capacity: usize) -> Self {
+        Self {
+            word: Vec::with_capacity(capacity),
+            roots: Vec::with_capacity(capacity),
+            policy: WordPolicy::default(),
+        }
+    }
+
+    /// The identity, simplifying the letters appended later according to the policy
+    pub fn id_with_policy(policy: WordPolicy) -> Self {
+        Self {
+            word: Vec::new(),
+            roots: Vec::new(),
+            policy,
+        }
+    }
+
+    /// Make from a slice
+    pub fn from_slice(perms: &[P]) -> Self {
+        perms.iter().cloned().collect()
+    }
+
+    pub fn from_perm(p: &P) -> Self {
+        iter::once(p.clone()).collect()
+    }
+
+    pub fn policy(&self) -> WordPolicy {
+        self.policy
+    }
+
+    /// Change the policy. It applies to the letters appended from now on
+    pub fn set_policy(&mut self, policy: WordPolicy) {
+        self.policy = policy;
+    }
+
+    /// Iterate over the permutations of the word, in the order they are applied
+    pub fn iter(&self) -> impl Iterator<Item = &P> {
+        self.word.iter()
+    }
+
+    /// Get an underlying permutation
+    pub fn evaluate(&self) -> P {
+        let n = self.lmp_upper().unwrap_or(0);
+        let image: Vec<usize> = (0..n + 1).map(|x| self.apply(x)).collect();
+        P::from_images(&image)
+    }
+
+    /// Check for equality on a general iterator. Note that equality on 0..=self.lmp() <= self.lmp_upper() will imply actual equality
+    pub fn eq_on_iter(&self, other: &Self, iter: impl IntoIterator<Item = usize>) -> bool {
+        iter.into_iter()
+            .map(|i| (self.apply(i), other.apply(i)))
+            .all(|(i, j)| i == j)
+    }
+
+    /// Check for equality on the given base.
+    pub fn eq_on_base(&self, other: &Self, base: &[usize]) -> bool {
+        self.eq_on_iter(other, base.iter().copied())
+    }
+
+    /// Check that this acts as the identity on a general iterator. Note that true on 0..=self.lmp() <+ self.lmp_upper() will imply it is actually the identity.
+    pub fn id_on_iter(&self, iter: impl IntoIterator<Item = usize>) -> bool {
+        iter.into_iter().all(|x| self.apply(x) == x)
+    }
+
+    /// Get an upper bound on the lmp. Note lmp_upper == None => self == id
+    pub fn lmp_upper(&self) -> Option<usize> {
+        self.word.iter().flat_map(|p| p.lmp()).max()
+    }
+
+    /// Lazily evaluate the inverse of the permutation, using the identify (ab)^-1 = b^-1a^-1
+    pub fn inv_lazy(&self) -> Self {
+        let mut inv = self.clone();
+        inv.inv_lazy_mut();
+        inv
+    }
+
+    /// Multiply in place.
+    pub fn multiply_mut(&mut self, other: &P) {
+        self.push_power(other.clone(), 1, other.clone());
+    }
+
+    /// Multiply in place by another word.
+    pub fn multiply_mut_word(&mut self, other: &Self) {
+        for (letter, (root, exp)) in other.word.iter().zip(other.roots.iter()) {
+            self.push_power(root.clone(), *exp, letter.clone());
+        }
+    }
+
+    // Invert word permutation lazily in place.
+    pub fn inv_lazy_mut(&mut self) {
+        // Reverse and take inverse, using (ab)^-1 = b^-1a^-1. The inverse of a reduced word is reduced.
+        self.word.reverse();
+        self.roots.reverse();
+
+        for p in self.word.iter_mut() {
+            *p = p.inv()
+        }
+
+        for (root, _) in self.roots.iter_mut() {
+            *root = root.inv()
+        }
+    }
+
+    // Append root^exp, whose value is power, simplifying it against the last letter as the policy allows
+    fn push_power(&mut self, root: P, exp: usize, power: P) {
+        if power.is_id() {
+            return;
+        }
+
+        let (merge, cancel) = match self.roots.last() {
+            Some((last_root, _)) if self.policy.merge_powers && same_letter(last_root, &root) => (true, false),
+            Some((last_root, _)) => (false, self.policy.free_reduction && inverse_letters(last_root, &root)),
+            None => (false, false),
+        };
+
+        if merge || cancel {
+            let last = self.word.pop().unwrap();
+            let (last_root, last_exp) = self.roots.pop().unwrap();
+            if merge {
+                let merged = last.multiply(&power);
+                if !merged.is_id() {
+                    self.word.push(merged);
+                    self.roots.push((root, last_exp + exp));
+                }
+            } else if exp < last_exp {
+                self.word.push(last.multiply(&power));
+                self.roots.push((last_root, last_exp - exp));
+            } else if exp > last_exp {
+                // What is left of the power is appended as any other letter
+                self.push_power(root, exp - last_exp, last.multiply(&power));
+            }
+            return;
+        }
+
+        self.word.push(power);
+        self.roots.push((root, exp));
+
+        if let Some(threshold) = self.policy.collapse_threshold {
+            if self.word.len() > threshold {
+                let collapsed = self.evaluate();
+                self.word.clear();
+                self.roots.clear();
+                if !collapsed.is_id() {
+                    self.word.push(collapsed.clone());
+                    self.roots.push((collapsed, 1));
+                }
+            }
+        }
+    }
+}
+
+// Most appended letters are unrelated to the last one, so the checks below first compare the largest moved point
+// and its image, which rules them out without going through every point
+fn same_letter<P: Permutation>(a: &P, b: &P) -> bool {
+    match a.lmp() {
+        Some(n) if Some(n) == b.lmp() => a.apply(n) == b.apply(n) && a == b,
+        n => n.is_none() && b.is_id(),
+    }
+}
+
+fn inverse_letters<P: Permutation>(a: &P, b: &P) -> bool {
+    match a.lmp() {
+        Some(n) if Some(n) == b.lmp() => a.apply(b.apply(n)) == n && (0..n).all(|x| a.apply(b.apply(x)) == x),
+        n => n.is_none() && b.is_id(),
+    }
+}
+
+impl<P> FromIterator<P> for LazyPerm<P>
+where
+    P: Permutation,
+{
+    fn from_iter<T: IntoIterator<Item = P>>(iter: T) -> Self {
+        let mut word = Self::id_with_capacity(0);
+        for p in iter {
+            word.multiply_mut(&p);
+        }
+        word
+    }
+}
+
+impl<P> IntoIterator for LazyPerm<P>
+where
+    P: Permutation,
+{
+    type Item = P;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.word.into_iter()
+    }
+}
+
+impl<P> PartialEq for LazyPerm<P>
+where
+    P: Permutation,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let lmp_s = self.lmp();
+        let lmp_o = other.lmp();
+
+        match (lmp_s, lmp_o) {
+            (Some(m), Some(n)) if m == n => self.eq_on_iter(other, 0..=n),
+            (None, None) => true,
+            (_, _) => false,
+        }
+    }
+}
+
+impl<P> std::hash::Hash for LazyPerm<P>
+where
+    P: Permutation,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (0..self.lmp().unwrap_or(0)).for_each(|x| self.apply(x).hash(state))
+    }
+}
+
+fn lmp_finder(candidate: usize, mut appl: impl FnMut(usize) -> usize) -> Option<usize> {
+    (0..=candidate)
+        .rev()
+        .map(|i| (i, appl(i)))
+        .find(|(i, j)| i != j)
+        .map(|r| r.0)
+}
+
+impl<P> Permutation for LazyPerm<P>
+where
+    P: Permutation,
+{
+    fn id() -> Self {
+        iter::empty().collect()
+    }
+
+    fn is_id(&self) -> bool {
+        self.lmp().is_none()
+    }
+
+    fn from_images(images: &[usize]) -> Self {
+        iter::once(P::from_images(images)).collect()
+    }
+
+    fn apply(&self, x: usize) -> usize {
+        self.word.iter().fold(x, |x, p| p.apply(x))
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        let mut product = self.clone();
+        product.multiply_mut_word(other);
+        product
+    }
+
+    fn inv(&self) -> Self {
+        std::iter::once(self.evaluate().inv()).collect()
+    }
+
+    fn lmp(&self) -> Option<usize> {
+        let candidate = self.lmp_upper();
+
+        // Then this is the identity
+        let candidate = candidate?;
+
+        // Build an iterator over the duplicates, then get the second element. If it is some, then the lmp is not unique and
+        // so we have a duplicate
+        let duplicates = self
+            .word
+            .iter()
+            .flat_map(|p| p.lmp())
+            .filter(|&lmp| lmp == candidate)
+            .nth(1)
+            .is_some();
+
+        if duplicates {
+            lmp_finder(candidate, |x| self.apply(x))
+        } else {
+            Some(candidate)
+        }
+    }
+
+    fn shift(&self, pos: usize) -> Self {
+        Self {
+            word: self.word.iter().map(|p| p.shift(pos)).collect(),
+            roots: self.roots.iter().map(|(root, exp)| (root.shift(pos), *exp)).collect(),
+            policy: self.policy,
+        }
+    }
+}
+
+impl<P> Display for LazyPerm<P>
+where
+    P: Permutation,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.word.is_empty() {
+            return write!(f, "{}", P::id());
+        }
+
+        let mut buf = String::new();
+
+        for perm in self.word.iter().take(self.word.len() - 1) {
+            buf.push_str(&perm.to_string());
+            buf.push_str(", ");
+        }
+
+        buf.push_str(&self.word[self.word.len() - 1].to_string());
+        write!(f, "{}", buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::{
+            DefaultPermutation,
+            Permutation,
+        },
+    };
+
+    // A word that keeps every letter
+    fn unreduced(perms: impl IntoIterator<Item = DefaultPermutation>) -> LazyPerm {
+        let mut word = LazyPerm::id_with_policy(WordPolicy::keep_all());
+        perms.into_iter().for_each(|p| word.multiply_mut(&p));
+        word
+    }
+
+    #[test]
+    fn inv_lazy() {
+        let images = [vec![0, 2, 1], vec![0, 1, 2, 4, 3], vec![0, 1, 2, 3, 4, 5, 7, 6]];
+        let perms = images.iter().map(|arr| DefaultPermutation::from_images(arr));
+        let perm = LazyPerm::from_iter(perms);
+        assert_eq!(perm.inv_lazy().evaluate(), perm.inv().evaluate());
+        let id = LazyPerm::<DefaultPermutation>::id();
+        assert_eq!(id.inv_lazy().evaluate(), id.inv().evaluate());
+    }
+
+    #[test]
+    fn lmp_identity() {
+        let id = LazyPerm::<DefaultPermutation>::id();
+        assert_eq!(id.lmp_upper(), None);
+        assert_eq!(id.lmp(), None);
+    }
+
+    #[test]
+    fn lmp_upper_bound() {
+        let images = [vec![0, 2, 1], vec![0, 1, 2, 4, 3], vec![0, 1, 2, 3, 4, 5, 7, 6]];
+        let perms = images.iter().map(|arr| DefaultPermutation::from_images(arr));
+        let perm = LazyPerm::from_iter(perms);
+        assert!(perm.lmp_upper().unwrap() >= perm.lmp().unwrap());
+        assert!(perm.lmp_upper().unwrap() >= 7);
+        assert_eq!(perm.lmp().unwrap(), 7);
+    }
+
+    #[test]
+    fn lmp_not_eq_ub() {
+        let images = [vec![0, 1, 2, 4, 3], vec![0, 1, 2, 4, 3]];
+        let perms = images.iter().map(|arr| DefaultPermutation::from_images(arr));
+        let perm = unreduced(perms);
+        assert!(perm.lmp_upper().unwrap() >= perm.lmp().unwrap_or(0));
+        assert!(perm.lmp_upper().unwrap() >= 4);
+        assert!(perm.is_id());
+        assert!(perm.lmp().is_none());
+    }
+
+    #[test]
+    fn lmp_not_eq_ub_inv() {
+        let perm = DefaultPermutation::from_images(&[1, 2, 3, 4, 5, 0]);
+        let perm = unreduced(vec![perm.inv(), perm]);
+        assert!(perm.lmp_upper().unwrap() >= perm.lmp().unwrap_or(0));
+        assert!(perm.lmp_upper().unwrap() >= 5);
+        assert!(perm.is_id());
+        assert!(perm.lmp().is_none());
+    }
+
+    #[test]
+    fn lazy_builders() {
+        use crate::perm::builder::{
+            join::MultiJoin,
+            PermBuilder,
+        };
+
+        let a = DefaultPermutation::from_images(&[1, 2, 0]);
+        let b = DefaultPermutation::from_images(&[0, 1, 3, 2]);
+        let expr = a.build_multiply(&b).build_divide(&a);
+        assert_eq!(expr.lazy().iter().count(), 3);
+        assert_eq!(expr.lazy().evaluate(), expr.collapse());
+        assert_eq!(a.build_pow(-1).lazy().evaluate(), a.inv());
+        assert_eq!(a.build_pow(5).lazy().evaluate(), a.pow(5));
+        assert!(a.build_pow(0).lazy().is_id());
+
+        let multi = MultiJoin::from_iter(vec![a.clone(), b.clone(), a.clone()]);
+        assert_eq!(multi.lazy(), LazyPerm::from_slice(&[a.clone(), b, a]));
+    }
+
+    #[test]
+    fn free_reduction() {
+        let a = DefaultPermutation::from_images(&[1, 2, 3, 0]);
+        let b = DefaultPermutation::from_images(&[1, 0]);
+        let word = LazyPerm::from_slice(&[a.clone(), b.clone(), b.inv(), a.inv()]);
+        assert_eq!(word.iter().count(), 0);
+        assert!(word.is_id());
+        assert_eq!(word.to_string(), DefaultPermutation::id().to_string());
+
+        let word = LazyPerm::from_slice(&[b.clone(), a.clone()]);
+        let product = word.multiply(&word.inv_lazy());
+        assert_eq!(product.iter().count(), 0);
+
+        let policy = WordPolicy::new().free_reduction(false).merge_powers(false);
+        let mut word = LazyPerm::id_with_policy(policy);
+        word.multiply_mut(&a);
+        word.multiply_mut(&a.inv());
+        assert_eq!(word.iter().count(), 2);
+        assert!(word.is_id());
+
+        // Letters that agree on their largest moved point are not necessarily inverses
+        let c = DefaultPermutation::from_images(&[1, 2, 0, 4, 3]);
+        let mut word = LazyPerm::id_with_policy(WordPolicy::new().merge_powers(false));
+        word.multiply_mut(&c);
+        word.multiply_mut(&c);
+        assert_eq!(word.iter().count(), 2);
+        assert_eq!(word.evaluate(), c.multiply(&c));
+    }
+
+    #[test]
+    fn merge_powers() {
+        let a = DefaultPermutation::from_images(&[1, 2, 3, 4, 0]);
+        let b = DefaultPermutation::from_images(&[1, 0]);
+        let word = LazyPerm::from_slice(&[b.clone(), a.clone(), a.clone(), a.clone()]);
+        assert_eq!(word.iter().count(), 2);
+        assert_eq!(word.evaluate(), b.multiply(&a.pow(3)));
+
+        // Letters that agree on their largest moved point are not necessarily equal
+        let c = DefaultPermutation::from_images(&[1, 0, 2, 4, 3]);
+        let d = DefaultPermutation::from_images(&[0, 1, 2, 4, 3]);
+        assert_eq!(LazyPerm::from_slice(&[c.clone(), d.clone()]).iter().count(), 2);
+
+        // a^3 a^-1 a^-1 leaves a, and a^5 is the identity
+        let mut reduced = word.clone();
+        reduced.multiply_mut_word(&LazyPerm::from_slice(&[a.inv(), a.inv()]));
+        assert_eq!(reduced.iter().count(), 2);
+        assert_eq!(reduced.evaluate(), b.multiply(&a));
+        reduced.multiply_mut_word(&LazyPerm::from_slice(&vec![a.clone(); 4]));
+        assert_eq!(reduced, LazyPerm::from_perm(&b));
+
+        // Cancelling a^3 against a^-4 leaves a^-1
+        let mut word = LazyPerm::from_slice(&[a.clone(), b.clone(), a.clone(), a.clone(), a.clone()]);
+        word.multiply_mut_word(&LazyPerm::from_slice(&[a.inv(), a.inv(), a.inv(), a.inv(), b.clone()]));
+        assert_eq!(word.iter().count(), 4);
+        assert_eq!(word.evaluate(), a.multiply(&b).multiply(&a.inv()).multiply(&b));
+    }
+
+    #[test]
+    fn collapse_threshold() {
+        let perms: Vec<_> = (0..6)
+            .map(|i| DefaultPermutation::from_images(&[1, 0]).shift(i))
+            .collect();
+        let policy = WordPolicy::keep_all().collapse_threshold(3);
+        let mut word = LazyPerm::id_with_policy(policy);
+        let mut product = DefaultPermutation::id();
+        for p in perms.iter() {
+            word.multiply_mut(p);
+            product = product.multiply(p);
+            assert!(word.iter().count() <= 3);
+            assert_eq!(word.evaluate(), product);
+        }
+        assert_eq!(word.policy(), policy);
+    }
+}
@@ -1,6 +1,12 @@
 pub mod join;
+pub mod lazy;
 pub mod pow;
 
+pub use lazy::{
+    LazyPerm,
+    WordPolicy,
+};
+
 use {
     crate::perm::Permutation,
     join::Join,
@@ -34,6 +40,12 @@ pub trait PermBuilder<P: Permutation>: Clone {
 
     /// Unfold all the layers and make a single permutation (Note, often it will be wanted to store this)
     fn collapse(&self) -> P;
+
+    /// Flatten into a lazy product, that can be applied and multiplied further without evaluating it.
+    /// Products keep their factors as letters, while other expressions are collapsed into a single letter.
+    fn lazy(&self) -> LazyPerm<P> {
+        LazyPerm::from_perm(&self.collapse())
+    }
 }
 
 impl<P> PermBuilder<P> for P
@@ -1,5 +1,8 @@
 use {
-    super::PermBuilder,
+    super::{
+        LazyPerm,
+        PermBuilder,
+    },
     crate::perm::Permutation,
 };
 
@@ -52,6 +55,17 @@ where
 
         pow(self.perm.collapse(), self.power as usize)
     }
+
+    fn lazy(&self) -> LazyPerm<P> {
+        // Inverses are taken letter by letter, and only larger powers are evaluated
+        let word = self.perm.lazy();
+        let word = if self.power < 0 { word.inv_lazy() } else { word };
+        match self.power.unsigned_abs() {
+            0 => LazyPerm::id(),
+            1 => word,
+            n => LazyPerm::from_perm(&pow(word.evaluate(), n)),
+        }
+    }
 }
 
 /// Recursive helper function, uses repeated exponentiation
@@ -65,7 +79,7 @@ pub fn pow<P: Permutation>(perm: P, n: usize) -> P {
         return perm;
     }
 
-    if n % 2 == 0 {
+    if n.is_multiple_of(2) {
         let p = pow(perm, n / 2);
         p.multiply(&p)
     } else {
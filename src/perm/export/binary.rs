@@ -0,0 +1,671 @@
+//! A compact binary format for groups, decorated groups and stabilizer chains.
+//!
+//! A file starts with the magic bytes `STBC` and a little endian `u16` version, followed by any number of items.
+//! Every item starts with a tag byte. Each permutation is stored as the number of points it acts on (`u32`)
+//! followed by their images, using the smallest of `u8`, `u16` and `u32` that fits the degree of the item.
+//!
+//! * Group: tag `0`, then the permutations.
+//! * Decorated group: tag `1`, the order as a `u32` length followed by its little endian bytes, then the permutations.
+//! * Stabilizer chain: tag `2`, the transversal kind (`0` simple, `1` factored), the degree, the width and the
+//!   number of layers. Each layer stores its base point, its generators and its transversal as (point, permutation)
+//!   pairs.
+//!
+//! Lists of permutations are stored as the degree (`u32`), the width in bytes (`u8`), the number of
+//! permutations (`u32`) and then the permutations. All integers are little endian.
+//!
+//! The reader and writer work on streams, so that libraries of groups never need to be loaded at once.
+
+use {
+    crate::{
+        group::{
+            group_library::DecoratedGroup,
+            orbit::abstraction::{
+                TransversalKind,
+                TransversalResolver,
+            },
+            stabchain::{
+                complete_stabchain,
+                valid_stabchain,
+                Stabchain,
+                StabchainRecord,
+            },
+            Group,
+        },
+        perm::{
+            Action,
+            Permutation,
+        },
+    },
+    num::BigUint,
+    std::{
+        convert::TryFrom,
+        io::{
+            self,
+            Read,
+            Write,
+        },
+    },
+};
+
+/// The bytes every file starts with
+pub const MAGIC: [u8; 4] = *b"STBC";
+
+/// The version of the format written by `BinaryWriter`
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The kinds of items that can be stored in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Group,
+    DecoratedGroup,
+    Stabchain,
+}
+
+impl ItemKind {
+    fn tag(self) -> u8 {
+        match self {
+            ItemKind::Group => 0,
+            ItemKind::DecoratedGroup => 1,
+            ItemKind::Stabchain => 2,
+        }
+    }
+
+    fn from_tag(tag: u8) -> io::Result<Self> {
+        match tag {
+            0 => Ok(ItemKind::Group),
+            1 => Ok(ItemKind::DecoratedGroup),
+            2 => Ok(ItemKind::Stabchain),
+            _ => Err(invalid_data(format!("unknown item tag {}", tag))),
+        }
+    }
+}
+
+/// Number of bytes used to store an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Width {
+    U8,
+    U16,
+    U32,
+}
+
+impl Width {
+    fn for_degree(degree: usize) -> io::Result<Self> {
+        if degree <= 1 << 8 {
+            Ok(Width::U8)
+        } else if degree <= 1 << 16 {
+            Ok(Width::U16)
+        } else if degree as u64 <= 1 << 32 {
+            Ok(Width::U32)
+        } else {
+            Err(invalid_data(format!("degree {} is too large", degree)))
+        }
+    }
+
+    fn bytes(self) -> u8 {
+        match self {
+            Width::U8 => 1,
+            Width::U16 => 2,
+            Width::U32 => 4,
+        }
+    }
+
+    fn from_bytes(bytes: u8) -> io::Result<Self> {
+        match bytes {
+            1 => Ok(Width::U8),
+            2 => Ok(Width::U16),
+            4 => Ok(Width::U32),
+            _ => Err(invalid_data(format!("invalid image width {}", bytes))),
+        }
+    }
+}
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+fn transversal_kind_tag(kind: TransversalKind) -> u8 {
+    match kind {
+        TransversalKind::Simple => 0,
+        TransversalKind::Factored => 1,
+    }
+}
+
+fn transversal_kind_from_tag(tag: u8) -> io::Result<TransversalKind> {
+    match tag {
+        0 => Ok(TransversalKind::Simple),
+        1 => Ok(TransversalKind::Factored),
+        _ => Err(invalid_data(format!("unknown transversal kind {}", tag))),
+    }
+}
+
+fn degree_of<'a, P: 'a + Permutation>(perms: impl IntoIterator<Item = &'a P>) -> usize {
+    perms.into_iter().flat_map(|p| p.lmp()).max().map_or(0, |lmp| lmp + 1)
+}
+
+/// Writes items to a stream in the binary format
+#[derive(Debug)]
+pub struct BinaryWriter<W> {
+    inner: W,
+}
+
+impl<W> BinaryWriter<W>
+where
+    W: Write,
+{
+    /// Create the writer, writing the header of the file
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        inner.write_all(&MAGIC)?;
+        inner.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        Ok(Self { inner })
+    }
+
+    /// Write a group
+    pub fn write_group<P: Permutation>(&mut self, g: &Group<P>) -> io::Result<()> {
+        self.write_u8(ItemKind::Group.tag())?;
+        self.write_perms(g.generators())
+    }
+
+    /// Write a group together with its order
+    pub fn write_decorated_group<P: Permutation>(&mut self, g: &DecoratedGroup<P>) -> io::Result<()> {
+        self.write_u8(ItemKind::DecoratedGroup.tag())?;
+        let order = g.order().to_bytes_le();
+        self.write_len(order.len())?;
+        self.inner.write_all(&order)?;
+        self.write_perms(g.group().generators())
+    }
+
+    /// Write a stabilizer chain
+    pub fn write_stabchain<P, V, A>(&mut self, chain: &Stabchain<P, V, A>) -> io::Result<()>
+    where
+        P: Permutation,
+        A: Action<P, OrbitT = usize>,
+        V: TransversalResolver<P, A>,
+    {
+        let degree = degree_of(chain.iter().flat_map(|record| {
+            record
+                .group()
+                .generators()
+                .iter()
+                .chain(record.transversal_map().values())
+        }))
+        .max(chain.iter().map(|record| record.base() + 1).max().unwrap_or(0));
+        let width = Width::for_degree(degree)?;
+
+        self.write_u8(ItemKind::Stabchain.tag())?;
+        self.write_u8(transversal_kind_tag(V::default().kind()))?;
+        self.write_len(degree)?;
+        self.write_u8(width.bytes())?;
+        self.write_len(chain.len())?;
+        for record in chain.iter() {
+            self.write_point(width, *record.base())?;
+
+            let gens = record.group().generators();
+            self.write_len(gens.len())?;
+            for p in gens {
+                self.write_perm(width, p)?;
+            }
+
+            self.write_len(record.transversal_map().len())?;
+            for (&point, p) in record.transversal_map() {
+                self.write_point(width, point)?;
+                self.write_perm(width, p)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flush the underlying stream
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Get the underlying stream back
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn write_perms<P: Permutation>(&mut self, perms: &[P]) -> io::Result<()> {
+        let degree = degree_of(perms);
+        let width = Width::for_degree(degree)?;
+        self.write_len(degree)?;
+        self.write_u8(width.bytes())?;
+        self.write_len(perms.len())?;
+        for p in perms {
+            self.write_perm(width, p)?;
+        }
+        Ok(())
+    }
+
+    fn write_perm<P: Permutation>(&mut self, width: Width, p: &P) -> io::Result<()> {
+        let degree = degree_of(std::iter::once(p));
+        self.write_len(degree)?;
+        for i in 0..degree {
+            self.write_point(width, p.apply(i))?;
+        }
+        Ok(())
+    }
+
+    fn write_point(&mut self, width: Width, x: usize) -> io::Result<()> {
+        match width {
+            Width::U8 => self.inner.write_all(&(x as u8).to_le_bytes()),
+            Width::U16 => self.inner.write_all(&(x as u16).to_le_bytes()),
+            Width::U32 => self.inner.write_all(&(x as u32).to_le_bytes()),
+        }
+    }
+
+    fn write_len(&mut self, len: usize) -> io::Result<()> {
+        let len = u32::try_from(len).map_err(|_| invalid_data(format!("length {} is too large", len)))?;
+        self.inner.write_all(&len.to_le_bytes())
+    }
+
+    fn write_u8(&mut self, x: u8) -> io::Result<()> {
+        self.inner.write_all(&[x])
+    }
+}
+
+/// Reads items from a stream in the binary format
+#[derive(Debug)]
+pub struct BinaryReader<R> {
+    inner: R,
+    version: u16,
+    peeked: Option<ItemKind>,
+}
+
+impl<R> BinaryReader<R>
+where
+    R: Read,
+{
+    /// Create the reader, checking the header of the file
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        inner.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid_data("not a stabchain binary file"));
+        }
+
+        let mut version = [0; 2];
+        inner.read_exact(&mut version)?;
+        let version = u16::from_le_bytes(version);
+        if version == 0 || version > FORMAT_VERSION {
+            return Err(invalid_data(format!("unsupported format version {}", version)));
+        }
+
+        Ok(Self {
+            inner,
+            version,
+            peeked: None,
+        })
+    }
+
+    /// The version of the format of the file being read
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Get the kind of the next item, or None if the end of the stream was reached
+    pub fn peek_kind(&mut self) -> io::Result<Option<ItemKind>> {
+        if self.peeked.is_none() {
+            let mut tag = [0];
+            if self.inner.read(&mut tag)? == 0 {
+                return Ok(None);
+            }
+            self.peeked = Some(ItemKind::from_tag(tag[0])?);
+        }
+        Ok(self.peeked)
+    }
+
+    /// Read the next item as a group, or None if the end of the stream was reached
+    pub fn read_group<P: Permutation>(&mut self) -> io::Result<Option<Group<P>>> {
+        if !self.next_item(ItemKind::Group)? {
+            return Ok(None);
+        }
+        Ok(Some(Group::new(&self.read_perms()?)))
+    }
+
+    /// Read the next item as a decorated group, or None if the end of the stream was reached
+    pub fn read_decorated_group<P: Permutation>(&mut self) -> io::Result<Option<DecoratedGroup<P>>> {
+        if !self.next_item(ItemKind::DecoratedGroup)? {
+            return Ok(None);
+        }
+        // The length comes from the file, so the order is read as it arrives rather than allocated upfront
+        let len = self.read_len()?;
+        let mut order = Vec::new();
+        (&mut self.inner).take(len as u64).read_to_end(&mut order)?;
+        if order.len() != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let group = Group::new(&self.read_perms()?);
+        Ok(Some(DecoratedGroup::new(group, BigUint::from_bytes_le(&order))))
+    }
+
+    /// Read the next item as a stabilizer chain, or None if the end of the stream was reached.
+    /// The chain is checked with `valid_stabchain` and `complete_stabchain`.
+    pub fn read_stabchain<P, V, A>(&mut self) -> io::Result<Option<Stabchain<P, V, A>>>
+    where
+        P: Permutation,
+        A: Action<P, OrbitT = usize>,
+        V: TransversalResolver<P, A>,
+    {
+        if !self.next_item(ItemKind::Stabchain)? {
+            return Ok(None);
+        }
+
+        let kind = transversal_kind_from_tag(self.read_u8()?)?;
+        if kind != V::default().kind() {
+            return Err(invalid_data(format!("expected a chain with a {:?} transversal", kind)));
+        }
+
+        let degree = self.read_len()?;
+        let width = Width::from_bytes(self.read_u8()?)?;
+        let layers = self.read_len()?;
+        // Not allocated upfront, as the number of layers comes from the file
+        let mut records = Vec::new();
+        for _ in 0..layers {
+            let base = self.read_point(width)?;
+
+            let n_gens = self.read_len()?;
+            let gens = (0..n_gens)
+                .map(|_| self.read_perm(degree, width))
+                .collect::<io::Result<Group<P>>>()?;
+
+            let orbit_len = self.read_len()?;
+            let transversal = (0..orbit_len)
+                .map(|_| Ok((self.read_point(width)?, self.read_perm(degree, width)?)))
+                .collect::<io::Result<_>>()?;
+
+            records.push(StabchainRecord::new(base, gens, transversal));
+        }
+
+        let chain = Stabchain::from_records(records);
+        valid_stabchain(&chain)
+            .and_then(|_| complete_stabchain(&chain))
+            .map_err(|e| invalid_data(format!("invalid stabilizer chain: {:?}", e)))?;
+        Ok(Some(chain))
+    }
+
+    /// Consume the tag of the next item, checking it has the expected kind
+    fn next_item(&mut self, expected: ItemKind) -> io::Result<bool> {
+        match self.peek_kind()? {
+            None => Ok(false),
+            Some(kind) if kind == expected => {
+                self.peeked = None;
+                Ok(true)
+            }
+            Some(kind) => Err(invalid_data(format!("expected a {:?}, found a {:?}", expected, kind))),
+        }
+    }
+
+    fn read_perms<P: Permutation>(&mut self) -> io::Result<Vec<P>> {
+        let degree = self.read_len()?;
+        let width = Width::from_bytes(self.read_u8()?)?;
+        let len = self.read_len()?;
+        (0..len).map(|_| self.read_perm(degree, width)).collect()
+    }
+
+    fn read_perm<P: Permutation>(&mut self, max_degree: usize, width: Width) -> io::Result<P> {
+        let degree = self.read_len()?;
+        if degree > max_degree {
+            return Err(invalid_data(format!(
+                "permutation on {} points in an item of degree {}",
+                degree, max_degree
+            )));
+        }
+        let images = (0..degree)
+            .map(|_| self.read_point(width))
+            .collect::<io::Result<Vec<_>>>()?;
+        crate::perm::utils::valid_images(&images[..]).map_err(|_| invalid_data("invalid permutation images"))?;
+        Ok(P::from_images(&images[..]))
+    }
+
+    fn read_point(&mut self, width: Width) -> io::Result<usize> {
+        Ok(match width {
+            Width::U8 => {
+                let mut buf = [0; 1];
+                self.inner.read_exact(&mut buf)?;
+                buf[0] as usize
+            }
+            Width::U16 => {
+                let mut buf = [0; 2];
+                self.inner.read_exact(&mut buf)?;
+                u16::from_le_bytes(buf) as usize
+            }
+            Width::U32 => {
+                let mut buf = [0; 4];
+                self.inner.read_exact(&mut buf)?;
+                u32::from_le_bytes(buf) as usize
+            }
+        })
+    }
+
+    fn read_len(&mut self) -> io::Result<usize> {
+        let mut buf = [0; 4];
+        self.inner.read_exact(&mut buf)?;
+        Ok(u32::from_le_bytes(buf) as usize)
+    }
+
+    fn read_u8(&mut self) -> io::Result<u8> {
+        let mut buf = [0; 1];
+        self.inner.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            group::{
+                orbit::abstraction::{
+                    FactoredTransversalResolver,
+                    SimpleTransversalResolver,
+                },
+                stabchain::{
+                    base::selectors::LmpSelector,
+                    builder::{
+                        IftBuilderStrategy,
+                        NaiveBuilderStrategy,
+                    },
+                },
+            },
+            perm::{
+                actions::SimpleApplication,
+                export::ExportablePermutation,
+                DefaultPermutation,
+            },
+        },
+    };
+
+    fn writer() -> BinaryWriter<Vec<u8>> {
+        BinaryWriter::new(Vec::new()).unwrap()
+    }
+
+    fn reader(bytes: &[u8]) -> BinaryReader<&[u8]> {
+        BinaryReader::new(bytes).unwrap()
+    }
+
+    #[test]
+    fn groups_round_trip() {
+        let groups = vec![
+            Group::trivial(),
+            Group::symmetric(5),
+            Group::dihedral_2n(200),
+            Group::cyclic(1000),
+        ];
+        let mut w = writer();
+        for g in &groups {
+            w.write_group(g).unwrap();
+        }
+        let bytes = w.into_inner();
+
+        let mut r = reader(&bytes);
+        assert_eq!(r.version(), FORMAT_VERSION);
+        for g in &groups {
+            assert_eq!(r.peek_kind().unwrap(), Some(ItemKind::Group));
+            let loaded: Group = r.read_group().unwrap().unwrap();
+            assert_eq!(loaded.generators(), g.generators());
+        }
+        assert_eq!(r.peek_kind().unwrap(), None);
+        assert!(r.read_group::<DefaultPermutation>().unwrap().is_none());
+    }
+
+    #[test]
+    fn smallest_width() {
+        let bytes_for = |g: &Group| {
+            let mut w = writer();
+            w.write_group(g).unwrap();
+            w.into_inner().len()
+        };
+        let cycle = |n: usize| {
+            let images: Vec<_> = (1..n).chain(std::iter::once(0)).collect();
+            Group::new(&[DefaultPermutation::from_images(&images[..])])
+        };
+        // Header, tag, degree, width, number of generators, length of the generator
+        let overhead = 6 + 1 + 4 + 1 + 4 + 4;
+        assert_eq!(bytes_for(&cycle(256)), overhead + 256);
+        assert_eq!(bytes_for(&cycle(257)), overhead + 2 * 257);
+        assert_eq!(bytes_for(&cycle(70000)), overhead + 4 * 70000);
+    }
+
+    #[test]
+    fn smaller_than_json() {
+        let g = Group::symmetric(100);
+        let mut w = writer();
+        w.write_group(&g).unwrap();
+        let json = serde_json::to_vec(&g.clone().map(ExportablePermutation::from)).unwrap();
+        assert!(w.into_inner().len() * 2 < json.len());
+    }
+
+    #[test]
+    fn decorated_groups_round_trip() {
+        let g = DecoratedGroup::from(Group::rubiks_cube());
+        let mut w = writer();
+        w.write_decorated_group(&g).unwrap();
+        w.write_group(&Group::klein_4()).unwrap();
+        let bytes = w.into_inner();
+
+        let mut r = reader(&bytes);
+        let loaded: DecoratedGroup = r.read_decorated_group().unwrap().unwrap();
+        assert_eq!(loaded.order(), g.order());
+        assert_eq!(loaded.group().generators(), g.group().generators());
+
+        // The next item is not a decorated group
+        assert!(r.read_decorated_group::<DefaultPermutation>().is_err());
+    }
+
+    #[test]
+    fn stabchains_round_trip() {
+        let g = Group::product(&Group::symmetric(5), &Group::alternating(4));
+        let naive = g.stabchain();
+        let ift = g.stabchain_with_strategy(IftBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let mut w = writer();
+        w.write_stabchain(&naive).unwrap();
+        w.write_stabchain(&ift).unwrap();
+        let bytes = w.into_inner();
+
+        let mut r = reader(&bytes);
+        let loaded: Stabchain<DefaultPermutation, SimpleTransversalResolver> = r.read_stabchain().unwrap().unwrap();
+        assert_eq!(loaded.order(), naive.order());
+        let loaded: Stabchain<DefaultPermutation, FactoredTransversalResolver<SimpleApplication<DefaultPermutation>>> =
+            r.read_stabchain().unwrap().unwrap();
+        assert_eq!(loaded.order(), ift.order());
+    }
+
+    #[test]
+    fn mismatched_transversal() {
+        let mut w = writer();
+        w.write_stabchain(&Group::symmetric(4).stabchain()).unwrap();
+        let bytes = w.into_inner();
+        assert!(reader(&bytes)
+            .read_stabchain::<DefaultPermutation, FactoredTransversalResolver<SimpleApplication<DefaultPermutation>>, _>()
+            .is_err());
+    }
+
+    #[test]
+    fn invalid_files() {
+        assert!(BinaryReader::new(&b"JSON{}"[..]).is_err());
+        assert!(BinaryReader::new(&b"STBC\x09\x00"[..]).is_err());
+
+        // Truncated group
+        let mut w = writer();
+        w.write_group(&Group::symmetric(5)).unwrap();
+        let bytes = w.into_inner();
+        assert!(reader(&bytes[..bytes.len() - 1])
+            .read_group::<DefaultPermutation>()
+            .is_err());
+
+        // Images that do not form a permutation
+        let mut bytes = bytes;
+        let len = bytes.len();
+        bytes[len - 1] = bytes[len - 2];
+        assert!(reader(&bytes).read_group::<DefaultPermutation>().is_err());
+
+        // A chain without its last layer
+        let chain: Stabchain<DefaultPermutation, SimpleTransversalResolver> = Group::symmetric(5)
+            .stabchain_with_strategy(NaiveBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        let truncated: Stabchain<_, _> = Stabchain::from_records(chain.iter().take(chain.len() - 1).cloned().collect());
+        let mut w = writer();
+        w.write_stabchain(&truncated).unwrap();
+        let bytes = w.into_inner();
+        let err = reader(&bytes)
+            .read_stabchain::<DefaultPermutation, SimpleTransversalResolver, SimpleApplication<DefaultPermutation>>()
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // Factored chains whose labels in the first layer swap 2 and 3 forever, or lead 2 out of the orbit
+        type IftChain =
+            Stabchain<DefaultPermutation, FactoredTransversalResolver<SimpleApplication<DefaultPermutation>>>;
+        let chain: IftChain = Group::symmetric(5)
+            .stabchain_with_strategy(IftBuilderStrategy::new(SimpleApplication::default(), LmpSelector));
+        assert_eq!(*chain.iter().next().unwrap().base(), 1);
+        for (points, label) in [(&[2, 3][..], &[0, 1, 3, 2][..]), (&[2][..], &[0, 1, 5, 3, 4, 2][..])] {
+            let mut records: Vec<_> = chain.iter().cloned().collect();
+            let mut transversal = records[0].transversal_map().clone();
+            for &point in points {
+                transversal.insert(point, DefaultPermutation::from_images(label));
+            }
+            records[0] = StabchainRecord::new(1, records[0].group().clone(), transversal);
+            let mut w = writer();
+            w.write_stabchain(&IftChain::from_records(records)).unwrap();
+            let bytes = w.into_inner();
+            let err = reader(&bytes)
+                .read_stabchain::<DefaultPermutation, FactoredTransversalResolver<_>, SimpleApplication<_>>()
+                .err()
+                .unwrap();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn huge_lengths() {
+        let header = |tag: u8| {
+            let mut bytes = writer().into_inner();
+            bytes.push(tag);
+            bytes
+        };
+
+        // A chain of degree 10 and width 1 that claims u32::MAX layers
+        let mut bytes = header(2);
+        bytes.push(0);
+        bytes.extend_from_slice(&10u32.to_le_bytes());
+        bytes.push(1);
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        let err = reader(&bytes)
+            .read_stabchain::<DefaultPermutation, SimpleTransversalResolver, SimpleApplication<DefaultPermutation>>()
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        // A decorated group whose order claims u32::MAX bytes
+        let mut bytes = header(1);
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 3]);
+        let err = reader(&bytes)
+            .read_decorated_group::<DefaultPermutation>()
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
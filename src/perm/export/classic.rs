@@ -1,8 +1,11 @@
 use {
     super::cycles::CyclePermutation,
-    crate::perm::{
-        impls::standard::StandardPermutation,
-        Permutation,
+    crate::{
+        perm::{
+            impls::standard::StandardPermutation,
+            Permutation,
+        },
+        Error,
     },
 };
 
@@ -32,10 +35,17 @@ impl ClassicalPermutation {
     /// Instantiate from a slice of images in the
     /// range [1, n]. Panics otherwise
     pub fn from_slice(images: &[usize]) -> Self {
-        assert!(images.iter().all(|&x| x != 0));
-        Self(StandardPermutation::from_images(
-            &images.iter().map(|i| i - 1).collect::<Vec<_>>()[..],
-        ))
+        Self::try_from_slice(images).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Instantiate from a slice of images in the range [1, n], or fail if they do not define a permutation
+    pub fn try_from_slice(images: &[usize]) -> Result<Self, Error> {
+        if images.contains(&0) {
+            return Err(Error::ZeroPoint);
+        }
+        Ok(Self(StandardPermutation::try_from_vec(
+            images.iter().map(|i| i - 1).collect(),
+        )?))
     }
 
     /// Gets the images of this permutation
@@ -111,11 +121,22 @@ macro_rules! impl_all {
 use crate::perm::impls::{
     based::BasedPermutation,
     map::MapPermutation,
+    small::SmallPermutation,
+    sparse::SparsePermutation,
     sync::SyncPermutation,
     word::WordPermutation,
 };
 
-impl_all!(BasedPermutation, MapPermutation, SyncPermutation, WordPermutation);
+impl_all!(
+    BasedPermutation,
+    MapPermutation,
+    SyncPermutation,
+    WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation
+);
 
 #[cfg(test)]
 mod tests {
@@ -148,6 +169,16 @@ mod tests {
         let _perm = ClassicalPermutation::from_slice(&[1, 3, 4, 2, 0]);
     }
 
+    #[test]
+    fn try_from_invalid_vector() {
+        assert_eq!(
+            ClassicalPermutation::try_from_slice(&[1, 3, 4, 2, 0]),
+            Err(Error::ZeroPoint)
+        );
+        assert!(ClassicalPermutation::try_from_slice(&[1, 3, 3]).is_err());
+        assert!(ClassicalPermutation::try_from_slice(&[1, 3, 2]).is_ok());
+    }
+
     #[test]
     fn application() {
         let perm = ClassicalPermutation::from_slice(&[1, 3, 4, 2]);
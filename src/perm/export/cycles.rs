@@ -1,6 +1,9 @@
 use {
     super::ClassicalPermutation,
-    crate::perm::Permutation,
+    crate::{
+        perm::Permutation,
+        Error,
+    },
     serde::{
         Deserialize,
         Serialize,
@@ -24,34 +27,33 @@ impl CyclePermutation {
 
     /// Take some images in the 1..=n range and output a cycled repr
     pub fn from_images(images: &[usize]) -> Self {
-        assert!(images.iter().all(|&n| n > 0));
+        Self::try_from_images(images).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        let classic = ClassicalPermutation::from_slice(images);
-        classic.into()
+    /// Take some images in the 1..=n range and output a cycled repr, or fail if they do not define a permutation
+    pub fn try_from_images(images: &[usize]) -> Result<Self, Error> {
+        Ok(ClassicalPermutation::try_from_slice(images)?.into())
     }
 
     pub fn from_vec(cycles: Vec<Vec<usize>>) -> Self {
-        use crate::DetHashMap;
-        // Check the element range
-        assert!(cycles.iter().flatten().all(|&i| i > 0));
-
-        // Get the maximal element in the permutation
-        let n = cycles.iter().flatten().max().cloned().unwrap_or(0);
-
-        if n == 0 {
-            return Self::from_vec_unchecked(cycles);
-        }
-
-        let mut counts = DetHashMap::default();
+        Self::try_from_vec(cycles).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        for i in cycles.iter().flatten() {
-            *counts.entry(*i).or_insert(0) += 1;
+    /// Build from disjoint cycles on 1..=n, or fail if a point is 0 or appears twice
+    pub fn try_from_vec(cycles: Vec<Vec<usize>>) -> Result<Self, Error> {
+        use crate::DetHashSet;
+        // Check the element range
+        if cycles.iter().flatten().any(|&i| i == 0) {
+            return Err(Error::ZeroPoint);
         }
 
         // Check every element occurs at most once
-        assert!(counts.values().all(|&i| i <= 1));
+        let mut seen = DetHashSet::default();
+        if let Some(&i) = cycles.iter().flatten().find(|&&i| !seen.insert(i)) {
+            return Err(Error::RepeatedPoint(i));
+        }
 
-        Self::from_vec_unchecked(cycles)
+        Ok(Self::from_vec_unchecked(cycles))
     }
 
     /// Get the order of the permutations
@@ -169,6 +171,8 @@ macro_rules! impl_all {
 use crate::perm::impls::{
     based::BasedPermutation,
     map::MapPermutation,
+    small::SmallPermutation,
+    sparse::SparsePermutation,
     standard::StandardPermutation,
     sync::SyncPermutation,
     word::WordPermutation,
@@ -179,7 +183,11 @@ impl_all!(
     SyncPermutation,
     MapPermutation,
     BasedPermutation,
-    WordPermutation
+    WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation
 );
 
 #[cfg(test)]
@@ -230,6 +238,20 @@ mod tests {
         let _cyclic = CyclePermutation::from_vec(vec![vec![1, 3, 4], vec![2, 4]]);
     }
 
+    #[test]
+    fn try_create_from_cycles() {
+        assert!(CyclePermutation::try_from_vec(vec![vec![1, 3], vec![2, 4]]).is_ok());
+        assert_eq!(
+            CyclePermutation::try_from_vec(vec![vec![1, 3], vec![2, 0]]).unwrap_err(),
+            Error::ZeroPoint
+        );
+        assert_eq!(
+            CyclePermutation::try_from_vec(vec![vec![1, 3, 4], vec![2, 4]]).unwrap_err(),
+            Error::RepeatedPoint(4)
+        );
+        assert!(CyclePermutation::try_from_images(&[2, 2, 1]).is_err());
+    }
+
     #[test]
     fn cyclical_to_classical_conversion_id() {
         let cyclic: ClassicalPermutation = CyclePermutation::from_vec(vec![]).into();
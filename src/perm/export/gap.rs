@@ -0,0 +1,201 @@
+//! Reading and writing permutations and groups in the syntax of GAP.
+//!
+//! Permutations are written as products of cycles such as `(1,2,3)(4,5)`, with `()` for the identity,
+//! and groups as `Group([(1,2,3),(1,2)])`. The parser also accepts the generators without the list brackets
+//! (`Group((1,2,3),(1,2))`), a bare list of generators, cycles that are not disjoint and the backslash line
+//! continuations GAP uses when printing long lines.
+
+use {
+    super::{
+        parse::{
+            Cursor,
+            ParseError,
+        },
+        CyclePermutation,
+    },
+    crate::{
+        group::Group,
+        perm::Permutation,
+    },
+    std::str::FromStr,
+};
+
+/// Parse a permutation written in GAP syntax
+/// ```
+/// use stabchain::perm::export::gap;
+/// let perm = gap::parse_permutation("(1,2,3)(4,5)").unwrap();
+/// assert_eq!(perm.cycles(), &[vec![1, 2, 3], vec![4, 5]]);
+/// ```
+pub fn parse_permutation(s: &str) -> Result<CyclePermutation, ParseError> {
+    let mut cursor = Cursor::new(s);
+    let perm = cursor.cycle_product()?;
+    cursor.finish()?;
+    Ok(perm)
+}
+
+/// Parse a group written in GAP syntax
+pub fn parse_group<P: Permutation>(s: &str) -> Result<Group<P>, ParseError> {
+    let mut cursor = Cursor::new(s);
+    let generators = if cursor.peek() == Some('[') {
+        cursor.list("[", "]", Cursor::cycle_product)?
+    } else {
+        cursor.expect("Group")?;
+        cursor.expect("(")?;
+        let generators = if cursor.peek() == Some('[') {
+            cursor.list("[", "]", Cursor::cycle_product)?
+        } else {
+            // Group((1,2),(3,4)) lists the generators as arguments
+            let mut generators = vec![cursor.cycle_product()?];
+            while cursor.eat(",") {
+                generators.push(cursor.cycle_product()?);
+            }
+            generators
+        };
+        cursor.expect(")")?;
+        generators
+    };
+    cursor.finish()?;
+
+    Ok(generators.into_iter().map(CyclePermutation::into_perm).collect())
+}
+
+/// Write a permutation in GAP syntax
+pub fn permutation_to_string(perm: &CyclePermutation) -> String {
+    if perm.cycles().is_empty() {
+        return "()".to_string();
+    }
+
+    perm.cycles()
+        .iter()
+        .map(|cycle| {
+            let points: Vec<_> = cycle.iter().map(|x| x.to_string()).collect();
+            format!("({})", points.join(","))
+        })
+        .collect()
+}
+
+/// Write a group in GAP syntax, i.e. `Group([(1,2,3),(1,2)])`
+pub fn group_to_string<P: Permutation>(g: &Group<P>) -> String {
+    let generators: Vec<_> = g
+        .generators()
+        .iter()
+        .map(|p| permutation_to_string(&CyclePermutation::from(p.clone())))
+        .collect();
+    format!("Group([{}])", generators.join(","))
+}
+
+impl FromStr for CyclePermutation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_permutation(s)
+    }
+}
+
+impl<P> FromStr for Group<P>
+where
+    P: Permutation,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_group(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::DefaultPermutation,
+    };
+
+    #[test]
+    fn parse_permutations() {
+        let perm: CyclePermutation = "(1,2,3)(4,5)".parse().unwrap();
+        assert_eq!(perm.cycles(), &[vec![1, 2, 3], vec![4, 5]]);
+
+        let perm: CyclePermutation = " ( 1 , 7 ) ".parse().unwrap();
+        assert_eq!(perm.cycles(), &[vec![1, 7]]);
+
+        let id: CyclePermutation = "()".parse().unwrap();
+        assert!(id.cycles().is_empty());
+    }
+
+    #[test]
+    fn parse_display_output() {
+        let perm = CyclePermutation::from_vec(vec![vec![1, 4, 2], vec![3, 5]]);
+        let parsed: CyclePermutation = perm.to_string().parse().unwrap();
+        assert_eq!(parsed.cycles(), perm.cycles());
+    }
+
+    #[test]
+    fn non_disjoint_cycles() {
+        // Multiplication is left to right, as in GAP
+        let perm: CyclePermutation = "(1,2)(2,3)".parse().unwrap();
+        assert_eq!(perm.cycles(), &[vec![1, 3, 2]]);
+    }
+
+    #[test]
+    fn parse_groups() {
+        let g: Group = "Group([(1,2,3),(1,2)])".parse().unwrap();
+        assert_eq!(g.stabchain().order(), 6u8.into());
+
+        let h: Group = "Group((1,2,3,4,5),\\\n(1,2))".parse().unwrap();
+        assert_eq!(h.stabchain().order(), 120u8.into());
+
+        let k: Group = "[(1,2)(3,4), (1,3)(2,4)]".parse().unwrap();
+        assert_eq!(k.stabchain().order(), 4u8.into());
+
+        let trivial: Group = "Group([])".parse().unwrap();
+        assert!(trivial.generators().is_empty());
+        let trivial: Group = "Group(())".parse().unwrap();
+        assert_eq!(trivial.stabchain().order(), 1u8.into());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = "(1,2".parse::<CyclePermutation>().unwrap_err();
+        assert_eq!(err.position(), 4);
+        assert_eq!(
+            err.to_string(),
+            "parse error at position 4: expected a number, found the end of the input"
+        );
+
+        let err = "(1,0)".parse::<CyclePermutation>().unwrap_err();
+        assert_eq!(err.position(), 3);
+
+        let err = "(1,2,1)".parse::<CyclePermutation>().unwrap_err();
+        assert_eq!(err.message(), "a point appears twice in the same cycle");
+
+        assert!("(1,2) x".parse::<CyclePermutation>().is_err());
+        assert!("Grp([(1,2)])".parse::<Group>().is_err());
+        assert!("Group([(1,2)]".parse::<Group>().is_err());
+        assert!("Group([(1,2);(2,3)])".parse::<Group>().is_err());
+    }
+
+    #[test]
+    fn write_gap() {
+        let perm = CyclePermutation::from_vec(vec![vec![1, 2, 3], vec![4, 5]]);
+        assert_eq!(permutation_to_string(&perm), "(1,2,3)(4,5)");
+        assert_eq!(permutation_to_string(&CyclePermutation::id()), "()");
+        assert_eq!(group_to_string(&Group::<DefaultPermutation>::new(&[])), "Group([])");
+    }
+
+    #[test]
+    fn round_trip_groups() {
+        let groups = vec![
+            Group::symmetric(6),
+            Group::alternating(7),
+            Group::dihedral_2n(9),
+            Group::klein_4(),
+            Group::rubiks_cube(),
+        ];
+        for g in groups {
+            let text = group_to_string(&g);
+            let parsed: Group = text.parse().unwrap();
+            assert_eq!(parsed.generators(), g.generators());
+            assert_eq!(group_to_string(&parsed), text);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Reading and writing permutations and groups in the notation of Magma.
+//!
+//! Permutations are written as products of cycles such as `(1, 2, 3)(4, 5)`, or as image lists `\[2, 3, 1]`,
+//! and groups as `PermutationGroup<5 | (1, 2, 3, 4, 5), (1, 2)>`, where the number is the degree.
+
+use {
+    super::{
+        parse::{
+            permutation_from_images,
+            Cursor,
+            ParseError,
+        },
+        CyclePermutation,
+    },
+    crate::{
+        group::Group,
+        perm::Permutation,
+    },
+};
+
+fn permutation(cursor: &mut Cursor) -> Result<CyclePermutation, ParseError> {
+    if cursor.peek() == Some('\\') {
+        let position = cursor.position();
+        cursor.expect("\\")?;
+        let images = cursor.list("[", "]", Cursor::number)?;
+        permutation_from_images(position, &images)
+    } else {
+        cursor.cycle_product()
+    }
+}
+
+/// Parse a permutation written in Magma notation
+pub fn parse_permutation(s: &str) -> Result<CyclePermutation, ParseError> {
+    let mut cursor = Cursor::new(s);
+    let perm = permutation(&mut cursor)?;
+    cursor.finish()?;
+    Ok(perm)
+}
+
+/// Parse a group written in Magma notation, checking that the generators act on the given degree
+/// ```
+/// use stabchain::{group::Group, perm::export::magma};
+/// let g: Group = magma::parse_group("PermutationGroup<4 | (1, 2, 3, 4), (1, 2)>").unwrap();
+/// assert_eq!(g.stabchain().order(), 24u8.into());
+/// ```
+pub fn parse_group<P: Permutation>(s: &str) -> Result<Group<P>, ParseError> {
+    let mut cursor = Cursor::new(s);
+    cursor.expect("PermutationGroup")?;
+    cursor.expect("<")?;
+    let degree = cursor.number()?;
+    cursor.expect("|")?;
+
+    let mut generators = Vec::new();
+    if !cursor.eat(">") {
+        loop {
+            let position = cursor.position();
+            let perm = permutation(&mut cursor)?;
+            if perm.cycles().iter().flatten().any(|&x| x > degree) {
+                return Err(ParseError::new(
+                    position,
+                    format!("generator moves points outside of the degree {}", degree),
+                ));
+            }
+            generators.push(perm);
+
+            if cursor.eat(">") {
+                break;
+            }
+            cursor.expect(",")?;
+        }
+    }
+    cursor.finish()?;
+
+    Ok(generators.into_iter().map(CyclePermutation::into_perm).collect())
+}
+
+/// Write a permutation in Magma notation
+pub fn permutation_to_string(perm: &CyclePermutation) -> String {
+    if perm.cycles().is_empty() {
+        return "()".to_string();
+    }
+
+    perm.cycles()
+        .iter()
+        .map(|cycle| {
+            let points: Vec<_> = cycle.iter().map(|x| x.to_string()).collect();
+            format!("({})", points.join(", "))
+        })
+        .collect()
+}
+
+/// Write a group in Magma notation, with the smallest degree the generators act on
+pub fn group_to_string<P: Permutation>(g: &Group<P>) -> String {
+    let generators: Vec<_> = g
+        .generators()
+        .iter()
+        .map(|p| permutation_to_string(&CyclePermutation::from(p.clone())))
+        .collect();
+    format!(
+        "PermutationGroup<{} | {}>",
+        g.symmetric_super_order(),
+        generators.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::{
+            export::parse::library_groups,
+            utils::random_permutation,
+            DefaultPermutation,
+        },
+    };
+
+    #[test]
+    fn parse_permutations() {
+        let perm = parse_permutation("(1, 2, 3)(4, 5)").unwrap();
+        assert_eq!(perm.cycles(), &[vec![1, 2, 3], vec![4, 5]]);
+
+        let perm = parse_permutation("\\[2, 3, 1, 4]").unwrap();
+        assert_eq!(perm.cycles(), &[vec![1, 2, 3]]);
+
+        assert!(parse_permutation("\\[2, 2, 1]").is_err());
+    }
+
+    #[test]
+    fn parse_groups() {
+        let g: Group = parse_group("PermutationGroup<5 | (1, 2, 3, 4, 5), \\[2, 1]>").unwrap();
+        assert_eq!(g.stabchain().order(), 120u8.into());
+
+        let trivial: Group = parse_group("PermutationGroup<3 | >").unwrap();
+        assert!(trivial.generators().is_empty());
+
+        let err = parse_group::<DefaultPermutation>("PermutationGroup<3 | (1, 4)>").unwrap_err();
+        assert_eq!(err.position(), 21);
+        assert!(parse_group::<DefaultPermutation>("PermutationGroup<3 (1, 2)>").is_err());
+        assert!(parse_group::<DefaultPermutation>("Group([(1,2)])").is_err());
+    }
+
+    #[test]
+    fn write_magma() {
+        let g: Group = Group::new(&[CyclePermutation::from_vec(vec![vec![1, 2], vec![3, 4]]).into_perm()]);
+        assert_eq!(group_to_string(&g), "PermutationGroup<4 | (1, 2)(3, 4)>");
+    }
+
+    #[test]
+    fn round_trip_library() {
+        for g in library_groups() {
+            let text = group_to_string(&g);
+            let parsed: Group = parse_group(&text).unwrap();
+            assert_eq!(parsed.generators(), g.generators());
+            assert_eq!(group_to_string(&parsed), text);
+        }
+    }
+
+    #[test]
+    fn round_trip_random_permutations() {
+        for n in 1..50 {
+            let perm = CyclePermutation::from(random_permutation::<DefaultPermutation>(n));
+            let parsed = parse_permutation(&permutation_to_string(&perm)).unwrap();
+            assert_eq!(parsed.cycles(), perm.cycles());
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Reading and writing permutations and groups in the notation of Mathematica.
+//!
+//! Permutations are written as `Cycles[{{1, 2, 3}, {4, 5}}]`, with `Cycles[{}]` for the identity,
+//! and groups as `PermutationGroup[{Cycles[{{1, 2}}], Cycles[{{1, 2, 3}}]}]`.
+
+use {
+    super::{
+        parse::{
+            product_of_cycles,
+            Cursor,
+            ParseError,
+        },
+        CyclePermutation,
+    },
+    crate::{
+        group::Group,
+        perm::Permutation,
+    },
+};
+
+fn permutation(cursor: &mut Cursor) -> Result<CyclePermutation, ParseError> {
+    let position = cursor.position();
+    cursor.expect("Cycles")?;
+    cursor.expect("[")?;
+    let cycles = cursor.list("{", "}", |cursor| cursor.list("{", "}", Cursor::number))?;
+    cursor.expect("]")?;
+    product_of_cycles(position, &cycles)
+}
+
+/// Parse a permutation written in Mathematica notation
+pub fn parse_permutation(s: &str) -> Result<CyclePermutation, ParseError> {
+    let mut cursor = Cursor::new(s);
+    let perm = permutation(&mut cursor)?;
+    cursor.finish()?;
+    Ok(perm)
+}
+
+/// Parse a group written in Mathematica notation
+/// ```
+/// use stabchain::{group::Group, perm::export::mathematica};
+/// let g: Group = mathematica::parse_group("PermutationGroup[{Cycles[{{1, 2, 3}}], Cycles[{{1, 2}}]}]").unwrap();
+/// assert_eq!(g.stabchain().order(), 6u8.into());
+/// ```
+pub fn parse_group<P: Permutation>(s: &str) -> Result<Group<P>, ParseError> {
+    let mut cursor = Cursor::new(s);
+    cursor.expect("PermutationGroup")?;
+    cursor.expect("[")?;
+    let generators = cursor.list("{", "}", permutation)?;
+    cursor.expect("]")?;
+    cursor.finish()?;
+
+    Ok(generators.into_iter().map(CyclePermutation::into_perm).collect())
+}
+
+/// Write a permutation in Mathematica notation
+pub fn permutation_to_string(perm: &CyclePermutation) -> String {
+    let cycles: Vec<_> = perm
+        .cycles()
+        .iter()
+        .map(|cycle| {
+            let points: Vec<_> = cycle.iter().map(|x| x.to_string()).collect();
+            format!("{{{}}}", points.join(", "))
+        })
+        .collect();
+    format!("Cycles[{{{}}}]", cycles.join(", "))
+}
+
+/// Write a group in Mathematica notation
+pub fn group_to_string<P: Permutation>(g: &Group<P>) -> String {
+    let generators: Vec<_> = g
+        .generators()
+        .iter()
+        .map(|p| permutation_to_string(&CyclePermutation::from(p.clone())))
+        .collect();
+    format!("PermutationGroup[{{{}}}]", generators.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::{
+            export::parse::library_groups,
+            utils::random_permutation,
+            DefaultPermutation,
+        },
+    };
+
+    #[test]
+    fn parse_permutations() {
+        let perm = parse_permutation("Cycles[{{1, 2, 3}, {4, 5}}]").unwrap();
+        assert_eq!(perm.cycles(), &[vec![1, 2, 3], vec![4, 5]]);
+        assert!(parse_permutation("Cycles[{}]").unwrap().cycles().is_empty());
+
+        let err = parse_permutation("Cycles[{{1, 2}, {3}").unwrap_err();
+        assert_eq!(err.position(), 19);
+        assert!(parse_permutation("Cycles[{{1, 2, 1}}]").is_err());
+        assert!(parse_permutation("(1,2)").is_err());
+    }
+
+    #[test]
+    fn parse_groups() {
+        let g: Group = parse_group("PermutationGroup[{Cycles[{{1, 2}, {3, 4}}], Cycles[{{1, 3}, {2, 4}}]}]").unwrap();
+        assert_eq!(g.stabchain().order(), 4u8.into());
+
+        let trivial: Group = parse_group("PermutationGroup[{}]").unwrap();
+        assert!(trivial.generators().is_empty());
+
+        assert!(parse_group::<DefaultPermutation>("PermutationGroup[{Cycles[{{1, 2}}]]").is_err());
+    }
+
+    #[test]
+    fn write_mathematica() {
+        assert_eq!(permutation_to_string(&CyclePermutation::id()), "Cycles[{}]");
+        let g: Group = Group::new(&[CyclePermutation::from_vec(vec![vec![1, 2], vec![3, 4]]).into_perm()]);
+        assert_eq!(group_to_string(&g), "PermutationGroup[{Cycles[{{1, 2}, {3, 4}}]}]");
+    }
+
+    #[test]
+    fn round_trip_library() {
+        for g in library_groups() {
+            let text = group_to_string(&g);
+            let parsed: Group = parse_group(&text).unwrap();
+            assert_eq!(parsed.generators(), g.generators());
+            assert_eq!(group_to_string(&parsed), text);
+        }
+    }
+
+    #[test]
+    fn round_trip_random_permutations() {
+        for n in 1..50 {
+            let perm = CyclePermutation::from(random_permutation::<DefaultPermutation>(n));
+            let parsed = parse_permutation(&permutation_to_string(&perm)).unwrap();
+            assert_eq!(parsed.cycles(), perm.cycles());
+        }
+    }
+}
@@ -1,5 +1,11 @@
+pub mod binary;
 mod classic;
 mod cycles;
+pub mod gap;
+pub mod magma;
+pub mod mathematica;
+mod parse;
+pub mod sage;
 
 use serde::{
     Deserialize,
@@ -9,6 +15,7 @@ use serde::{
 pub use {
     classic::ClassicalPermutation,
     cycles::CyclePermutation,
+    parse::ParseError,
 };
 
 use crate::perm::Permutation;
@@ -26,6 +33,22 @@ where
     }
 }
 
+impl ExportablePermutation {
+    /// Convert into any permutation type
+    pub fn into_perm<P: Permutation>(self) -> P {
+        self.try_into_perm().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Convert into any permutation type, or fail if the images on 1..=n do not define a permutation
+    pub fn try_into_perm<P: Permutation>(self) -> Result<P, crate::Error> {
+        if self.0.contains(&0) {
+            return Err(crate::Error::ZeroPoint);
+        }
+        let images: Vec<_> = self.0.iter().map(|i| i - 1).collect();
+        P::try_from_images(&images[..])
+    }
+}
+
 impl From<ClassicalPermutation> for ExportablePermutation {
     fn from(perm: ClassicalPermutation) -> Self {
         Self(perm.images())
@@ -36,8 +59,7 @@ macro_rules! impl_from_for_perm {
     ($name:ty) => {
         impl From<ExportablePermutation> for $name {
             fn from(perm: ExportablePermutation) -> Self {
-                let vec: Vec<_> = perm.0.iter().map(|i| i - 1).collect();
-                <$name>::from_images(&vec[..])
+                perm.into_perm()
             }
         }
     };
@@ -52,6 +74,8 @@ macro_rules! impl_all {
 use super::impls::{
     based::BasedPermutation,
     map::MapPermutation,
+    small::SmallPermutation,
+    sparse::SparsePermutation,
     standard::StandardPermutation,
     sync::SyncPermutation,
     word::WordPermutation,
@@ -62,5 +86,9 @@ impl_all!(
     SyncPermutation,
     MapPermutation,
     BasedPermutation,
-    WordPermutation
+    WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation
 );
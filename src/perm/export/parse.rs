@@ -0,0 +1,238 @@
+//! Shared utilities for parsing the text notations of other computer algebra systems.
+
+use {
+    super::CyclePermutation,
+    crate::perm::{
+        impls::standard::StandardPermutation,
+        Permutation,
+    },
+    std::{
+        error::Error,
+        fmt,
+    },
+};
+
+/// An error while parsing a permutation or a group, with the byte offset at which it occurred
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+
+    /// The byte offset in the input where the error occurred
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// A description of the error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse error at position {}: {}", self.position, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A cursor over the input, which skips whitespace between tokens
+#[derive(Debug)]
+pub(crate) struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    /// Skip whitespace, as well as the backslash line continuations that GAP emits for long lines
+    pub(crate) fn skip_whitespace(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if let Some(after) = trimmed.strip_prefix('\\') {
+                if after.starts_with('\n') || after.starts_with("\r\n") {
+                    self.pos += 1;
+                    continue;
+                }
+            }
+            return;
+        }
+    }
+
+    /// Look at the next token character
+    pub(crate) fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    /// Consume the given token if it comes next
+    pub(crate) fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume the given token, failing if it does not come next
+    pub(crate) fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", token)))
+        }
+    }
+
+    /// Parse a positive integer
+    pub(crate) fn number(&mut self) -> Result<usize, ParseError> {
+        self.skip_whitespace();
+        let digits = self.rest().chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(self.unexpected("a number"));
+        }
+
+        let start = self.pos;
+        let n: usize = self.rest()[..digits]
+            .parse()
+            .map_err(|_| ParseError::new(start, "number is too large"))?;
+        if n == 0 {
+            return Err(ParseError::new(start, "points are numbered from 1"));
+        }
+        self.pos += digits;
+        Ok(n)
+    }
+
+    /// Check that only whitespace is left
+    pub(crate) fn finish(&mut self) -> Result<(), ParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected("the end of the input")),
+        }
+    }
+
+    /// The current position in the input
+    pub(crate) fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// An error complaining that something else was expected at the current position
+    pub(crate) fn unexpected(&mut self, expected: &str) -> ParseError {
+        let found = match self.peek() {
+            Some(c) => format!("`{}`", c),
+            None => "the end of the input".to_string(),
+        };
+        ParseError::new(self.pos, format!("expected {}, found {}", expected, found))
+    }
+
+    /// Parse a comma separated list of items between the given delimiters, allowing an empty list
+    pub(crate) fn list<T>(
+        &mut self,
+        open: &str,
+        close: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.expect(open)?;
+        let mut items = Vec::new();
+        if self.eat(close) {
+            return Ok(items);
+        }
+
+        loop {
+            items.push(item(self)?);
+            if self.eat(close) {
+                return Ok(items);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    /// Parse a permutation as a product of cycles such as `(1,2,3)(4,5)`, or `()` for the identity.
+    /// Points in a cycle can be separated by commas or whitespace.
+    pub(crate) fn cycle_product(&mut self) -> Result<CyclePermutation, ParseError> {
+        let start = self.position();
+        self.expect("(")?;
+        if self.eat(")") {
+            return Ok(CyclePermutation::id());
+        }
+
+        let mut cycles = Vec::new();
+        loop {
+            let mut cycle = vec![self.number()?];
+            while !self.eat(")") {
+                self.eat(",");
+                cycle.push(self.number()?);
+            }
+            cycles.push(cycle);
+
+            if !self.eat("(") {
+                break;
+            }
+        }
+
+        product_of_cycles(start, &cycles)
+    }
+}
+
+/// Multiply the cycles in order. Unlike `CyclePermutation::from_vec`, the cycles do not need to be disjoint,
+/// which is how other systems read products such as `(1,2)(2,3)`.
+pub(crate) fn product_of_cycles(position: usize, cycles: &[Vec<usize>]) -> Result<CyclePermutation, ParseError> {
+    let mut product = StandardPermutation::id();
+    for cycle in cycles {
+        let mut sorted = cycle.clone();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|w| w[0] == w[1]) {
+            return Err(ParseError::new(position, "a point appears twice in the same cycle"));
+        }
+        if sorted.first() == Some(&0) {
+            return Err(ParseError::new(position, "points are numbered from 1"));
+        }
+
+        let cycle: StandardPermutation = CyclePermutation::single_cycle(cycle).into();
+        product = product.multiply(&cycle);
+    }
+    Ok(product.into())
+}
+
+/// Build a permutation from its images on 1..=n, as given by a list such as `[2,3,1]`
+pub(crate) fn permutation_from_images(position: usize, images: &[usize]) -> Result<CyclePermutation, ParseError> {
+    CyclePermutation::try_from_images(images)
+        .map_err(|_| ParseError::new(position, "the images do not define a permutation"))
+}
+
+/// Groups from the library with their generators, used to check that the notations round-trip
+#[cfg(test)]
+pub(crate) fn library_groups() -> Vec<crate::group::Group> {
+    use crate::group::Group;
+
+    vec![
+        Group::trivial(),
+        Group::symmetric(6),
+        Group::alternating(7),
+        Group::cyclic(12),
+        Group::dihedral_2n(9),
+        Group::klein_4(),
+        Group::product(&Group::symmetric(3), &Group::cyclic(5)),
+        Group::rubiks_cube(),
+    ]
+}
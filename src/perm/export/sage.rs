@@ -0,0 +1,184 @@
+//! Reading and writing permutations and groups in the notation of Sage.
+//!
+//! Sage accepts many ways of writing the generators of a `PermutationGroup`: lists of cycles given as tuples
+//! (`[(1,2,3),(4,5)]`), single tuples (`(1,2,3)`), strings in cycle notation (`'(1,2,3)(4,5)'`) and lists of images
+//! (`[2,3,1]`). All of these are parsed, and groups are written as `PermutationGroup([[(1,2,3)], [(1,2)]])`.
+
+use {
+    super::{
+        parse::{
+            permutation_from_images,
+            product_of_cycles,
+            Cursor,
+            ParseError,
+        },
+        CyclePermutation,
+    },
+    crate::{
+        group::Group,
+        perm::Permutation,
+    },
+};
+
+fn permutation(cursor: &mut Cursor) -> Result<CyclePermutation, ParseError> {
+    let position = cursor.position();
+    match cursor.peek() {
+        Some(quote @ '\'') | Some(quote @ '"') => {
+            let quote = quote.to_string();
+            cursor.expect(&quote)?;
+            let perm = cursor.cycle_product()?;
+            cursor.expect(&quote)?;
+            Ok(perm)
+        }
+        Some('(') => cursor.cycle_product(),
+        Some('[') => {
+            cursor.expect("[")?;
+            if cursor.eat("]") {
+                return Ok(CyclePermutation::id());
+            }
+
+            if cursor.peek() == Some('(') {
+                let mut cycles = vec![tuple(cursor)?];
+                while cursor.eat(",") {
+                    cycles.push(tuple(cursor)?);
+                }
+                cursor.expect("]")?;
+                product_of_cycles(position, &cycles)
+            } else {
+                let mut images = vec![cursor.number()?];
+                while cursor.eat(",") {
+                    images.push(cursor.number()?);
+                }
+                cursor.expect("]")?;
+                permutation_from_images(position, &images)
+            }
+        }
+        _ => Err(cursor.unexpected("a permutation")),
+    }
+}
+
+/// A single cycle written as a tuple, allowing Python's trailing comma as in `(1,)`
+fn tuple(cursor: &mut Cursor) -> Result<Vec<usize>, ParseError> {
+    cursor.expect("(")?;
+    let mut cycle = vec![cursor.number()?];
+    while cursor.eat(",") {
+        if cursor.peek() == Some(')') {
+            break;
+        }
+        cycle.push(cursor.number()?);
+    }
+    cursor.expect(")")?;
+    Ok(cycle)
+}
+
+/// Parse a permutation written in Sage notation
+pub fn parse_permutation(s: &str) -> Result<CyclePermutation, ParseError> {
+    let mut cursor = Cursor::new(s);
+    let perm = permutation(&mut cursor)?;
+    cursor.finish()?;
+    Ok(perm)
+}
+
+/// Parse a group written in Sage notation
+/// ```
+/// use stabchain::{group::Group, perm::export::sage};
+/// let g: Group = sage::parse_group("PermutationGroup([[(1,2),(3,4)], '(1,2,3)'])").unwrap();
+/// assert_eq!(g.stabchain().order(), 12u8.into());
+/// ```
+pub fn parse_group<P: Permutation>(s: &str) -> Result<Group<P>, ParseError> {
+    let mut cursor = Cursor::new(s);
+    cursor.expect("PermutationGroup")?;
+    cursor.expect("(")?;
+    let generators = cursor.list("[", "]", permutation)?;
+    cursor.expect(")")?;
+    cursor.finish()?;
+
+    Ok(generators.into_iter().map(CyclePermutation::into_perm).collect())
+}
+
+/// Write a permutation in Sage notation, as a list of cycles
+pub fn permutation_to_string(perm: &CyclePermutation) -> String {
+    let cycles: Vec<_> = perm
+        .cycles()
+        .iter()
+        .map(|cycle| {
+            let points: Vec<_> = cycle.iter().map(|x| x.to_string()).collect();
+            format!("({})", points.join(","))
+        })
+        .collect();
+    format!("[{}]", cycles.join(", "))
+}
+
+/// Write a group in Sage notation
+pub fn group_to_string<P: Permutation>(g: &Group<P>) -> String {
+    let generators: Vec<_> = g
+        .generators()
+        .iter()
+        .map(|p| permutation_to_string(&CyclePermutation::from(p.clone())))
+        .collect();
+    format!("PermutationGroup([{}])", generators.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::{
+            export::parse::library_groups,
+            utils::random_permutation,
+            DefaultPermutation,
+        },
+    };
+
+    #[test]
+    fn parse_permutations() {
+        let expected = [vec![1, 2, 3], vec![4, 5]];
+        for s in &["[(1,2,3),(4,5)]", "'(1,2,3)(4,5)'", "\"(1,2,3)(4,5)\"", "[2,3,1,5,4]"] {
+            assert_eq!(parse_permutation(s).unwrap().cycles(), &expected[..]);
+        }
+
+        assert_eq!(parse_permutation("(1,2,3)").unwrap().cycles(), &[vec![1, 2, 3]]);
+        assert!(parse_permutation("[(1,)]").unwrap().cycles().is_empty());
+        assert!(parse_permutation("[]").unwrap().cycles().is_empty());
+        assert!(parse_permutation("'()'").unwrap().cycles().is_empty());
+    }
+
+    #[test]
+    fn parse_groups() {
+        let g: Group = parse_group("PermutationGroup([[(1,2)]])").unwrap();
+        assert_eq!(g.stabchain().order(), 2u8.into());
+
+        let g: Group = parse_group("PermutationGroup([(1,2,3,4,5), [2,1]])").unwrap();
+        assert_eq!(g.stabchain().order(), 120u8.into());
+
+        assert!(parse_group::<DefaultPermutation>("PermutationGroup([[(1,2)]]").is_err());
+        assert!(parse_group::<DefaultPermutation>("PermutationGroup([[(1,2)], '(1,2]')").is_err());
+        assert!(parse_group::<DefaultPermutation>("PermutationGroup([[2,3]])").is_err());
+    }
+
+    #[test]
+    fn write_sage() {
+        assert_eq!(permutation_to_string(&CyclePermutation::id()), "[]");
+        let g: Group = Group::new(&[CyclePermutation::from_vec(vec![vec![1, 2], vec![3, 4]]).into_perm()]);
+        assert_eq!(group_to_string(&g), "PermutationGroup([[(1,2), (3,4)]])");
+    }
+
+    #[test]
+    fn round_trip_library() {
+        for g in library_groups() {
+            let text = group_to_string(&g);
+            let parsed: Group = parse_group(&text).unwrap();
+            assert_eq!(parsed.generators(), g.generators());
+            assert_eq!(group_to_string(&parsed), text);
+        }
+    }
+
+    #[test]
+    fn round_trip_random_permutations() {
+        for n in 1..50 {
+            let perm = CyclePermutation::from(random_permutation::<DefaultPermutation>(n));
+            let parsed = parse_permutation(&permutation_to_string(&perm)).unwrap();
+            assert_eq!(parsed.cycles(), perm.cycles());
+        }
+    }
+}
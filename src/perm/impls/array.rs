@@ -0,0 +1,285 @@
+use crate::perm::{
+    export::{
+        ClassicalPermutation,
+        CyclePermutation,
+        ExportablePermutation,
+    },
+    impls::{
+        based::BasedPermutation,
+        map::MapPermutation,
+        small::SmallPermutation,
+        sparse::SparsePermutation,
+        standard::StandardPermutation,
+        sync::SyncPermutation,
+        word::WordPermutation,
+    },
+    Permutation,
+};
+
+use std::{
+    fmt::Display,
+    iter::FromIterator,
+};
+
+/// Represents a permutation of degree at most N, as an array of images stored inline.
+/// It is `Copy`, and never allocates nor counts references, which makes it the fastest choice for tiny groups.
+/// The points from N onwards are fixed, and permutations that move them (for example through `shift`) panic.
+/// N has to be at most 256, as images are stored as bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Hash)]
+pub struct ArrayPermutation<const N: usize> {
+    vals: [u8; N],
+}
+
+impl<const N: usize> ArrayPermutation<N> {
+    // Evaluated when the type is used, so that a too large N fails to compile
+    const FITS: () = assert!(N <= 256, "ArrayPermutation can have at most 256 points");
+
+    pub fn as_array(&self) -> &[u8; N] {
+        &self.vals
+    }
+
+    /// Build from the images, or fail if they do not define a permutation or move a point from N onwards
+    pub fn try_from_slice(images: &[usize]) -> Result<Self, crate::Error> {
+        crate::perm::utils::valid_images(images)?;
+        let mut perm = Self::id();
+        for (i, &image) in images.iter().enumerate() {
+            if image != i {
+                if i >= N {
+                    return Err(crate::Error::PointTooLarge(i));
+                }
+                perm.vals[i] = image as u8;
+            }
+        }
+        Ok(perm)
+    }
+}
+
+impl<const N: usize> Permutation for ArrayPermutation<N> {
+    fn from_images(images: &[usize]) -> Self {
+        Self::try_from_slice(images).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_from_images(images: &[usize]) -> Result<Self, crate::Error> {
+        Self::try_from_slice(images)
+    }
+
+    fn id() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::FITS;
+        let mut vals = [0; N];
+        for (i, val) in vals.iter_mut().enumerate() {
+            *val = i as u8;
+        }
+        Self { vals }
+    }
+
+    fn is_id(&self) -> bool {
+        self.lmp().is_none()
+    }
+
+    fn apply(&self, x: usize) -> usize {
+        if x < N {
+            self.vals[x] as usize
+        } else {
+            x
+        }
+    }
+
+    fn inv(&self) -> Self {
+        let mut vals = [0; N];
+        for (i, &val) in self.vals.iter().enumerate() {
+            vals[val as usize] = i as u8;
+        }
+        Self { vals }
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        let mut vals = [0; N];
+        for (val, &x) in vals.iter_mut().zip(self.vals.iter()) {
+            *val = other.vals[x as usize];
+        }
+        Self { vals }
+    }
+
+    fn lmp(&self) -> Option<usize> {
+        self.vals.iter().enumerate().rposition(|(i, &val)| val as usize != i)
+    }
+
+    fn shift(&self, k: usize) -> Self {
+        let lmp = match self.lmp() {
+            Some(lmp) => lmp,
+            None => return Self::id(),
+        };
+
+        assert!(lmp + k < N, "{}", crate::Error::PointTooLarge(lmp + k));
+        let mut shifted = Self::id();
+        for i in 0..=lmp {
+            shifted.vals[i + k] = self.vals[i] + k as u8;
+        }
+        shifted
+    }
+}
+
+impl<const N: usize> Display for ArrayPermutation<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", CyclePermutation::from(*self))
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for ArrayPermutation<N> {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        Self::from_images(&iter.into_iter().collect::<Vec<_>>()[..])
+    }
+}
+
+// The conversion macros of the other types work on concrete types, so these are generic over N instead
+macro_rules! impl_array_conversions {
+    ($($other:ty), *) => {
+        $(
+        impl<const N: usize> From<$other> for ArrayPermutation<N> {
+            fn from(p: $other) -> Self {
+                Self::from_images(&p.images()[..])
+            }
+        }
+
+        impl<const N: usize> From<ArrayPermutation<N>> for $other {
+            fn from(p: ArrayPermutation<N>) -> Self {
+                <$other>::from_images(&p.images()[..])
+            }
+        }
+        )*
+    };
+}
+
+impl_array_conversions!(
+    StandardPermutation,
+    SyncPermutation,
+    BasedPermutation,
+    MapPermutation,
+    WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation
+);
+
+impl<const N: usize> From<ClassicalPermutation> for ArrayPermutation<N> {
+    fn from(perm: ClassicalPermutation) -> Self {
+        ExportablePermutation::from(perm).into_perm()
+    }
+}
+
+impl<const N: usize> From<CyclePermutation> for ArrayPermutation<N> {
+    fn from(perm: CyclePermutation) -> Self {
+        ClassicalPermutation::from(perm).into()
+    }
+}
+
+impl<const N: usize> From<ExportablePermutation> for ArrayPermutation<N> {
+    fn from(perm: ExportablePermutation) -> Self {
+        perm.into_perm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            group::{
+                stabchain::{
+                    base::selectors::{
+                        FmpSelector,
+                        LmpSelector,
+                    },
+                    builder::*,
+                    valid_stabchain,
+                },
+                Group,
+            },
+            perm::actions::SimpleApplication,
+            Error,
+        },
+        num::BigUint,
+    };
+
+    type Perm = ArrayPermutation<16>;
+
+    #[test]
+    fn inline_storage() {
+        assert_eq!(std::mem::size_of::<Perm>(), 16);
+        let perm = Perm::from_images(&[1, 2, 0]);
+        let copy = perm;
+        assert_eq!(perm.multiply(&copy), perm.inv());
+    }
+
+    #[test]
+    fn points_outside_the_array() {
+        let images: Vec<_> = (1..=16).chain(std::iter::once(0)).collect();
+        assert_eq!(Perm::try_from_images(&images[..]), Err(Error::PointTooLarge(16)));
+
+        let images: Vec<_> = [1, 0].iter().copied().chain(2..100).collect();
+        assert_eq!(Perm::from_images(&images[..]), Perm::from_images(&[1, 0]));
+        assert_eq!(Perm::from_images(&[1, 0]).apply(100), 100);
+    }
+
+    #[test]
+    fn shift_within_the_array() {
+        let perm = Perm::from_images(&[1, 2, 0]);
+        assert_eq!(perm.shift(13).lmp(), Some(15));
+        assert!(Perm::id().shift(100).is_id());
+    }
+
+    #[test]
+    #[should_panic]
+    fn shift_outside_the_array() {
+        Perm::from_images(&[1, 2, 0]).shift(14);
+    }
+
+    #[test]
+    fn conversions() {
+        let perm = StandardPermutation::from_images(&[3, 0, 4, 1, 2]);
+        let array: Perm = perm.clone().into();
+        assert_eq!(StandardPermutation::from(array), perm);
+        assert_eq!(array.to_string(), perm.to_string());
+        assert_eq!(Perm::from(CyclePermutation::from(perm)), array);
+    }
+
+    // A group on 16 points, with its order
+    fn group() -> (Group<Perm>, BigUint) {
+        let g = Group::product(&Group::symmetric(6), &Group::dihedral_2n(5)).map(Perm::from);
+        let g = Group::product(&g, &Group::cyclic(5).map(Perm::from));
+        (g, BigUint::from(720u32 * 10 * 5))
+    }
+
+    #[test]
+    fn orbits() {
+        let (g, _) = group();
+        assert_eq!(g.orbit(0).len(), 6);
+        assert_eq!(g.orbit(7).len(), 5);
+        assert_eq!(g.orbit(15).len(), 5);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn all_strategies() {
+        let (g, order) = group();
+        let action = SimpleApplication::default;
+        let chain = g.stabchain_with_strategy(NaiveBuilderStrategy::new(action(), LmpSelector));
+        valid_stabchain(&chain).unwrap();
+        assert_eq!(chain.order(), order);
+        let chain = g.stabchain_with_strategy(IftBuilderStrategy::new(action(), LmpSelector));
+        valid_stabchain(&chain).unwrap();
+        assert_eq!(chain.order(), order);
+        let chain = g.stabchain_with_strategy(RandomBuilderStrategyNaive::new(action(), FmpSelector));
+        valid_stabchain(&chain).unwrap();
+        let chain = g.stabchain_with_strategy(RandomBuilderStrategyShallow::new(action(), FmpSelector));
+        valid_stabchain(&chain).unwrap();
+        #[cfg(feature = "parallel")]
+        {
+            let chain = g.stabchain_with_strategy(ParallelBuilderStrategy::new(action(), LmpSelector));
+            valid_stabchain(&chain).unwrap();
+            assert_eq!(chain.order(), order);
+        }
+    }
+}
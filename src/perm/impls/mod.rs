@@ -1,5 +1,9 @@
+pub mod array;
 pub mod based;
 pub mod map;
+pub mod slp;
+pub mod small;
+pub mod sparse;
 pub mod standard;
 pub mod sync;
 pub mod word;
@@ -22,6 +26,24 @@ macro_rules! permutation_tests {
                 <$name>::from_images(&[0, 1, 2, 2]);
             }
 
+            #[test]
+            fn try_invalid_images() {
+                use crate::{
+                    perm::utils::ImageError,
+                    Error,
+                };
+
+                assert_eq!(
+                    <$name>::try_from_images(&[1, 2, 3]),
+                    Err(Error::InvalidImages(ImageError::MissingValue(0)))
+                );
+                assert!(<$name>::try_from_images(&[0, 1, 2, 2]).is_err());
+                assert_eq!(
+                    <$name>::try_from_images(&[1, 0]),
+                    Ok(<$name>::from_images(&[1, 0]))
+                );
+            }
+
             #[test]
             fn id_perm() {
                 assert_eq!(<$name>::id(), <$name>::id());
@@ -190,6 +212,37 @@ macro_rules! permutation_tests {
                 assert_eq!(*cycle2, id.divide(cycle));
             }
 
+            #[test]
+            fn cycle_structure() {
+                let id = <$name>::id();
+                assert_eq!(id.cycles().count(), 0);
+                assert!(id.cycle_type().is_empty());
+                assert_eq!(id.num_cycles(), 0);
+                assert_eq!(id.sign(), 1);
+                assert!(id.support().is_empty());
+                assert!(id.fixed_points().is_empty());
+                assert_eq!(id.order(), 1);
+
+                // (0 1 2)(4 5)
+                let perm = <$name>::from_images(&[1, 2, 0, 3, 5, 4, 6]);
+                assert_eq!(perm.cycles().collect::<Vec<_>>(), vec![vec![0, 1, 2], vec![4, 5]]);
+                assert_eq!(perm.cycle_type(), vec![3, 2]);
+                assert_eq!(perm.num_cycles(), 2);
+                assert_eq!(perm.parity(), 1);
+                assert_eq!(perm.sign(), -1);
+                assert_eq!(perm.support(), vec![0, 1, 2, 4, 5]);
+                assert_eq!(perm.fixed_points(), vec![3]);
+                assert_eq!(perm.order(), 6);
+
+                // (0 1 ... 12)(13 14), past the degree where order used to switch algorithm
+                let images: Vec<_> = (1..13).chain([0, 14, 13].iter().copied()).collect();
+                let perm = <$name>::from_images(&images[..]);
+                assert_eq!(perm.cycle_type(), vec![13, 2]);
+                assert_eq!(perm.sign(), -1);
+                assert_eq!(perm.order(), 26);
+                assert_eq!(perm.pow(26), id);
+            }
+
             #[test]
             fn test_shift_identity() {
                 let p = <$name>::id();
@@ -213,4 +266,10 @@ mod tests {
     permutation_tests!(crate::perm::impls::map::MapPermutation, map);
     permutation_tests!(crate::perm::impls::sync::SyncPermutation, sync);
     permutation_tests!(crate::perm::impls::word::WordPermutation, word);
+    permutation_tests!(crate::perm::impls::slp::SlpPermutation, slp);
+    permutation_tests!(crate::perm::impls::small::SmallPermutation<u8>, small_u8);
+    permutation_tests!(crate::perm::impls::small::SmallPermutation<u16>, small_u16);
+    permutation_tests!(crate::perm::impls::small::SmallPermutation<u32>, small_u32);
+    permutation_tests!(crate::perm::impls::array::ArrayPermutation<16>, array);
+    permutation_tests!(crate::perm::impls::sparse::SparsePermutation, sparse);
 }
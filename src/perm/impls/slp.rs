@@ -0,0 +1,279 @@
+use {
+    crate::{
+        perm::{
+            slp::{
+                Slp,
+                SlpLine,
+            },
+            DefaultPermutation,
+            Permutation,
+        },
+        DetHashMap,
+    },
+    std::{
+        fmt,
+        rc::Rc,
+    },
+};
+
+/// The expression tree that produced a permutation. Nodes are shared, so that the
+/// tree is really a DAG and can be flattened into a straight-line program.
+#[derive(Debug)]
+enum SlpNode {
+    Identity,
+    Generator(usize),
+    Product(Rc<SlpNode>, Rc<SlpNode>),
+    Inverse(Rc<SlpNode>),
+    // The permutation was created from its images, so it has no known word
+    Untracked,
+}
+
+/// A permutation that keeps track of how it was computed from a list of generators.
+/// Every multiplication and inversion is recorded, so that once the permutation is computed
+/// a straight-line program in the generators can be extracted with `slp`.
+/// Equality, hashing and ordering only depend on the underlying permutation.
+/// Note that `from_images` (and so `WordPermutation::evaluate`) loses the word, so chains that
+/// should be used for factorization need to be built with a deterministic strategy.
+#[derive(Clone)]
+pub struct SlpPermutation<P = DefaultPermutation> {
+    perm: P,
+    word: Rc<SlpNode>,
+}
+
+impl<P> SlpPermutation<P>
+where
+    P: Permutation,
+{
+    /// Wrap the i-th generator of a group
+    pub fn generator(perm: P, i: usize) -> Self {
+        Self {
+            perm,
+            word: Rc::new(SlpNode::Generator(i)),
+        }
+    }
+
+    /// Wrap a permutation for which no word is known
+    pub fn untracked(perm: P) -> Self {
+        let word = if perm.is_id() {
+            SlpNode::Identity
+        } else {
+            SlpNode::Untracked
+        };
+
+        Self {
+            perm,
+            word: Rc::new(word),
+        }
+    }
+
+    /// Get the underlying permutation
+    pub fn perm(&self) -> &P {
+        &self.perm
+    }
+
+    /// Discard the word and get the underlying permutation
+    pub fn into_perm(self) -> P {
+        self.perm
+    }
+
+    /// Is the word that computes this permutation known
+    pub fn is_tracked(&self) -> bool {
+        self.slp().is_some()
+    }
+
+    /// Flatten the recorded computation into a straight-line program in the generators.
+    /// Returns None if the permutation was (partially) computed from an untracked permutation.
+    pub fn slp(&self) -> Option<Slp> {
+        if let SlpNode::Identity = *self.word {
+            return Some(Slp::identity());
+        }
+
+        let mut lines = Vec::new();
+        let mut indices: DetHashMap<*const SlpNode, usize> = DetHashMap::default();
+        // Iterative post order traversal, as the trees can get pretty deep
+        let mut to_visit: Vec<(&SlpNode, bool)> = vec![(&*self.word, false)];
+        while let Some((node, expanded)) = to_visit.pop() {
+            let key = node as *const SlpNode;
+            if indices.contains_key(&key) {
+                continue;
+            }
+
+            let line = match (node, expanded) {
+                (SlpNode::Generator(i), _) => SlpLine::Generator(*i),
+                (SlpNode::Product(a, b), true) => SlpLine::Product(indices[&Rc::as_ptr(a)], indices[&Rc::as_ptr(b)]),
+                (SlpNode::Product(a, b), false) => {
+                    to_visit.push((node, true));
+                    to_visit.push((&**b, false));
+                    to_visit.push((&**a, false));
+                    continue;
+                }
+                (SlpNode::Inverse(a), true) => SlpLine::Inverse(indices[&Rc::as_ptr(a)]),
+                (SlpNode::Inverse(a), false) => {
+                    to_visit.push((node, true));
+                    to_visit.push((&**a, false));
+                    continue;
+                }
+                // Identities are never stored inside of other nodes
+                (SlpNode::Identity, _) | (SlpNode::Untracked, _) => return None,
+            };
+
+            indices.insert(key, lines.len());
+            lines.push(line);
+        }
+
+        Some(Slp::from_lines(lines))
+    }
+
+    fn product_node(a: &Rc<SlpNode>, b: &Rc<SlpNode>) -> Rc<SlpNode> {
+        match (&**a, &**b) {
+            (SlpNode::Identity, _) => b.clone(),
+            (_, SlpNode::Identity) => a.clone(),
+            _ => Rc::new(SlpNode::Product(a.clone(), b.clone())),
+        }
+    }
+
+    fn inverse_node(a: &Rc<SlpNode>) -> Rc<SlpNode> {
+        match &**a {
+            SlpNode::Identity => a.clone(),
+            SlpNode::Inverse(inner) => inner.clone(),
+            _ => Rc::new(SlpNode::Inverse(a.clone())),
+        }
+    }
+}
+
+impl<P> Permutation for SlpPermutation<P>
+where
+    P: Permutation,
+{
+    fn from_images(images: &[usize]) -> Self {
+        Self::untracked(P::from_images(images))
+    }
+
+    fn id() -> Self {
+        Self {
+            perm: P::id(),
+            word: Rc::new(SlpNode::Identity),
+        }
+    }
+
+    fn is_id(&self) -> bool {
+        self.perm.is_id()
+    }
+
+    fn apply(&self, x: usize) -> usize {
+        self.perm.apply(x)
+    }
+
+    fn inv(&self) -> Self {
+        Self {
+            perm: self.perm.inv(),
+            word: Self::inverse_node(&self.word),
+        }
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        Self {
+            perm: self.perm.multiply(&other.perm),
+            word: Self::product_node(&self.word, &other.word),
+        }
+    }
+
+    fn order(&self) -> usize {
+        self.perm.order()
+    }
+
+    /// Note that the word is kept, as it is still valid if the generators are shifted as well.
+    fn shift(&self, pos: usize) -> Self {
+        Self {
+            perm: self.perm.shift(pos),
+            word: self.word.clone(),
+        }
+    }
+
+    fn lmp(&self) -> Option<usize> {
+        self.perm.lmp()
+    }
+}
+
+impl<P> PartialEq for SlpPermutation<P>
+where
+    P: Permutation,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.perm == other.perm
+    }
+}
+
+impl<P> Eq for SlpPermutation<P> where P: Permutation {}
+
+impl<P> std::hash::Hash for SlpPermutation<P>
+where
+    P: Permutation,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.perm.hash(state);
+    }
+}
+
+// The word can be huge, so it is not printed
+impl<P> fmt::Debug for SlpPermutation<P>
+where
+    P: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlpPermutation").field("perm", &self.perm).finish()
+    }
+}
+
+impl<P> fmt::Display for SlpPermutation<P>
+where
+    P: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.perm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::export::CyclePermutation,
+    };
+
+    fn gens() -> Vec<SlpPermutation> {
+        vec![
+            SlpPermutation::generator(CyclePermutation::single_cycle(&[1, 2]).into_perm(), 0),
+            SlpPermutation::generator(CyclePermutation::single_cycle(&[1, 2, 3, 4, 5]).into_perm(), 1),
+        ]
+    }
+
+    #[test]
+    fn tracked_products() {
+        let gens = gens();
+        let p = gens[0].multiply(&gens[1]).pow(3).multiply(&gens[1].inv());
+        let slp = p.slp().unwrap();
+        let raw: Vec<_> = gens.iter().map(|g| g.perm().clone()).collect();
+        assert_eq!(&slp.evaluate(&raw), p.perm());
+    }
+
+    #[test]
+    fn shared_nodes_are_reused() {
+        let gens = gens();
+        let mut p = gens[0].multiply(&gens[1]);
+        for _ in 0..10 {
+            p = p.multiply(&p);
+        }
+        // Squaring ten times only adds ten lines
+        assert_eq!(p.slp().unwrap().len(), 13);
+    }
+
+    #[test]
+    fn untracked_words() {
+        let gens = gens();
+        let untracked = SlpPermutation::untracked(CyclePermutation::single_cycle(&[2, 3]).into_perm());
+        assert!(!untracked.is_tracked());
+        assert!(!gens[0].multiply(&untracked).is_tracked());
+        assert!(SlpPermutation::<DefaultPermutation>::untracked(DefaultPermutation::id()).is_tracked());
+    }
+}
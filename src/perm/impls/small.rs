@@ -0,0 +1,294 @@
+use crate::perm::{
+    export::CyclePermutation,
+    Permutation,
+};
+
+use std::{
+    fmt::{
+        Debug,
+        Display,
+    },
+    hash::Hash,
+    iter::FromIterator,
+    sync::Arc,
+};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type that can be used to store the images of a permutation.
+/// It is implemented for `u8`, `u16` and `u32`, and sealed as the permutations rely on the conversions being exact.
+pub trait ImageWidth: private::Sealed + Copy + Ord + Hash + Debug + Send + Sync + 'static {
+    /// The largest point that fits in the type
+    const MAX: usize;
+
+    /// Convert from a point, that is assumed to fit
+    fn from_point(x: usize) -> Self;
+
+    /// Convert back to a point
+    fn to_point(self) -> usize;
+
+    /// Push `table[i]` to `out` for every `i` in `indices`, which have to be in bounds of the table
+    #[doc(hidden)]
+    unsafe fn gather(table: &[Self], indices: &[Self], out: &mut Vec<Self>) {
+        out.extend(indices.iter().map(|&i| *table.get_unchecked(i.to_point())));
+    }
+
+    /// The inverse of images that define a permutation
+    #[doc(hidden)]
+    unsafe fn invert(vals: &[Self]) -> Vec<Self> {
+        let mut invvals = vec![Self::from_point(0); vals.len()];
+        for (i, val) in vals.iter().enumerate() {
+            *invvals.get_unchecked_mut(val.to_point()) = Self::from_point(i);
+        }
+        invvals
+    }
+}
+
+// The kernels, if any, are the vectorized versions of gather and invert for the width
+macro_rules! impl_image_width {
+    ($width:ty $(, gather = $gather:path)? $(, invert = $invert:path)?) => {
+        impl private::Sealed for $width {}
+
+        impl ImageWidth for $width {
+            const MAX: usize = <$width>::MAX as usize;
+
+            fn from_point(x: usize) -> Self {
+                x as $width
+            }
+
+            fn to_point(self) -> usize {
+                self as usize
+            }
+
+            $(
+            #[cfg(feature = "simd")]
+            unsafe fn gather(table: &[Self], indices: &[Self], out: &mut Vec<Self>) {
+                $gather(table, indices, out)
+            }
+            )?
+
+            $(
+            #[cfg(feature = "simd")]
+            unsafe fn invert(vals: &[Self]) -> Vec<Self> {
+                $invert(vals)
+            }
+            )?
+        }
+    };
+}
+
+impl_image_width!(u8, gather = crate::perm::simd::gather_u8);
+impl_image_width!(u16);
+impl_image_width!(
+    u32,
+    gather = crate::perm::simd::gather_u32,
+    invert = crate::perm::simd::invert_u32
+);
+
+/// Represents a permutation, storing the images in a narrower integer type than usize.
+/// Like Sync, the inverse is stored as well and the permutation can be sent between threads.
+/// The largest moved point has to fit in the image type.
+#[derive(Clone, Debug)]
+pub struct SmallPermutation<T> {
+    vals: Arc<[T]>,
+    invvals: Arc<[T]>,
+}
+
+impl<T: ImageWidth> SmallPermutation<T> {
+    pub fn as_vec(&self) -> &[T] {
+        &self.vals[..]
+    }
+
+    pub fn from_vec(vals: Vec<usize>) -> Self {
+        Self::try_from_vec(vals).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Build from the images, or fail if they do not define a permutation or do not fit in the image type
+    pub fn try_from_vec(mut vals: Vec<usize>) -> Result<Self, crate::Error> {
+        crate::perm::utils::valid_images(&vals[..])?;
+        while !vals.is_empty() && vals[vals.len() - 1] == vals.len() - 1 {
+            vals.pop();
+        }
+
+        match vals.len().checked_sub(1) {
+            Some(lmp) if lmp > T::MAX => Err(crate::Error::PointTooLarge(lmp)),
+            _ => Ok(Self::from_vec_unchecked(vals.into_iter().map(T::from_point).collect())),
+        }
+    }
+
+    fn from_vec_unchecked(mut vals: Vec<T>) -> Self {
+        while !vals.is_empty() && vals[vals.len() - 1].to_point() == vals.len() - 1 {
+            vals.pop();
+        }
+
+        // Safety: the images define a permutation
+        let invvals = unsafe { T::invert(&vals) };
+
+        Self {
+            vals: vals.into(),
+            invvals: invvals.into(),
+        }
+    }
+}
+
+impl<T: ImageWidth> Permutation for SmallPermutation<T> {
+    fn from_images(images: &[usize]) -> Self {
+        Self::from_vec(images.into())
+    }
+
+    fn try_from_images(images: &[usize]) -> Result<Self, crate::Error> {
+        Self::try_from_vec(images.into())
+    }
+
+    fn id() -> Self {
+        Self {
+            vals: Arc::new([]),
+            invvals: Arc::new([]),
+        }
+    }
+
+    fn is_id(&self) -> bool {
+        self.vals.is_empty()
+    }
+
+    fn apply(&self, x: usize) -> usize {
+        if x < self.vals.len() {
+            self.vals[x].to_point()
+        } else {
+            x
+        }
+    }
+
+    fn inv(&self) -> Self {
+        Self {
+            vals: self.invvals.clone(),
+            invvals: self.vals.clone(),
+        }
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        if self.is_id() {
+            other.clone()
+        } else if other.is_id() {
+            self.clone()
+        } else {
+            let self_size = self.vals.len();
+            let other_size = other.vals.len();
+            // Same special cases as Sync, to skip the bounds checks on the rhs when it is of larger degree
+            let v: Vec<T> = if self_size <= other_size {
+                let mut result = Vec::with_capacity(other_size);
+                // Safety: the images of self are smaller than self_size
+                unsafe { T::gather(&other.vals, &self.vals, &mut result) };
+                result.extend_from_slice(&other.vals[self_size..]);
+                result
+            } else {
+                self.vals
+                    .iter()
+                    .map(|&x| match other.vals.get(x.to_point()) {
+                        Some(&y) => y,
+                        None => x,
+                    })
+                    .collect()
+            };
+            Self::from_vec_unchecked(v)
+        }
+    }
+
+    fn lmp(&self) -> Option<usize> {
+        if self.vals.is_empty() {
+            None
+        } else {
+            Some(self.vals.len() - 1)
+        }
+    }
+
+    fn shift(&self, k: usize) -> Self {
+        if self.is_id() {
+            return Self::id();
+        }
+
+        let lmp = self.vals.len() - 1 + k;
+        assert!(lmp <= T::MAX, "{}", crate::Error::PointTooLarge(lmp));
+        let mut images: Vec<_> = (0..k).map(T::from_point).collect();
+        images.extend(self.vals.iter().map(|i| T::from_point(i.to_point() + k)));
+        Self::from_vec_unchecked(images)
+    }
+}
+
+impl<T: ImageWidth> PartialEq for SmallPermutation<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vals == other.vals
+    }
+}
+
+impl<T: ImageWidth> Eq for SmallPermutation<T> {}
+
+impl<T: ImageWidth> PartialOrd for SmallPermutation<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.vals.cmp(&other.vals))
+    }
+}
+
+impl<T: ImageWidth> std::hash::Hash for SmallPermutation<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.vals.hash(state);
+    }
+}
+
+impl<T: ImageWidth> Display for SmallPermutation<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", CyclePermutation::from(self.clone()))
+    }
+}
+
+impl<T: ImageWidth> FromIterator<usize> for SmallPermutation<T> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            perm::impls::standard::StandardPermutation,
+            Error,
+        },
+    };
+
+    #[test]
+    fn too_large() {
+        let images: Vec<_> = (1..=256).chain(std::iter::once(0)).collect();
+        assert_eq!(
+            SmallPermutation::<u8>::try_from_images(&images[..]),
+            Err(Error::PointTooLarge(256))
+        );
+        assert!(SmallPermutation::<u16>::try_from_images(&images[..]).is_ok());
+    }
+
+    #[test]
+    fn trailing_fixed_points_fit() {
+        let images: Vec<_> = [1, 0].iter().copied().chain(2..1000).collect();
+        let perm = SmallPermutation::<u8>::from_images(&images[..]);
+        assert_eq!(perm.lmp(), Some(1));
+        assert_eq!(perm.apply(999), 999);
+    }
+
+    #[test]
+    #[should_panic]
+    fn shift_too_large() {
+        SmallPermutation::<u8>::from_images(&[1, 0]).shift(255);
+    }
+
+    #[test]
+    fn conversions() {
+        let perm = StandardPermutation::from_images(&[3, 0, 4, 1, 2]);
+        let small: SmallPermutation<u16> = perm.clone().into();
+        assert_eq!(small.as_vec(), &[3, 0, 4, 1, 2]);
+        assert_eq!(StandardPermutation::from(small.clone()), perm);
+        assert_eq!(small.to_string(), perm.to_string());
+    }
+}
@@ -0,0 +1,271 @@
+use crate::perm::Permutation;
+
+use {
+    num::integer::lcm,
+    std::{
+        fmt,
+        iter::FromIterator,
+        sync::Arc,
+    },
+};
+
+/// Represents a permutation by its moved points and their images, sorted by point.
+/// Application is a binary search, and multiplication and inversion take O(s log s) for s moved points, so that
+/// the cost depends on the number of moved points rather than the degree. It is the best choice for
+/// permutations of huge degree that move few points. The moved points are shared, so cloning is cheap,
+/// and the permutation can be sent between threads.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Hash)]
+pub struct SparsePermutation {
+    moved: Arc<[(usize, usize)]>,
+}
+
+impl SparsePermutation {
+    /// The moved points with their images, sorted by point
+    pub fn as_pairs(&self) -> &[(usize, usize)] {
+        &self.moved[..]
+    }
+
+    /// Build from pairs of a point and its image, in any order. Unlike `from_images`, this does not need
+    /// space for the fixed points below the largest moved one.
+    pub fn from_pairs(pairs: Vec<(usize, usize)>) -> Self {
+        Self::try_from_pairs(pairs).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Build from pairs of a point and its image, or fail if a point is repeated or the images are
+    /// not a permutation of the points
+    pub fn try_from_pairs(mut pairs: Vec<(usize, usize)>) -> Result<Self, crate::Error> {
+        use crate::perm::utils::ImageError;
+
+        pairs.retain(|&(x, y)| x != y);
+        pairs.sort_unstable();
+        if let Some(pair) = pairs.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(crate::Error::RepeatedPoint(pair[0].0));
+        }
+
+        let mut images: Vec<_> = pairs.iter().map(|&(_, y)| y).collect();
+        images.sort_unstable();
+        for (&(x, _), &y) in pairs.iter().zip(images.iter()) {
+            if x != y {
+                // The smallest point where the two sets differ
+                let error = if x < y {
+                    ImageError::MissingValue(x)
+                } else {
+                    ImageError::DuplicatedImage(y)
+                };
+                return Err(error.into());
+            }
+        }
+
+        Ok(Self::from_sorted_unchecked(pairs))
+    }
+
+    fn from_sorted_unchecked(moved: Vec<(usize, usize)>) -> Self {
+        Self { moved: moved.into() }
+    }
+
+    /// The lengths of the non-trivial cycles
+    fn cycle_lengths(&self) -> Vec<usize> {
+        self.cycles().map(|cycle| cycle.len()).collect()
+    }
+}
+
+impl Permutation for SparsePermutation {
+    fn from_images(images: &[usize]) -> Self {
+        Self::try_from_images(images).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_from_images(images: &[usize]) -> Result<Self, crate::Error> {
+        crate::perm::utils::valid_images(images)?;
+        Ok(Self::from_sorted_unchecked(
+            images.iter().copied().enumerate().filter(|&(x, y)| x != y).collect(),
+        ))
+    }
+
+    fn id() -> Self {
+        Self { moved: Arc::new([]) }
+    }
+
+    fn is_id(&self) -> bool {
+        self.moved.is_empty()
+    }
+
+    fn apply(&self, x: usize) -> usize {
+        match self.moved.binary_search_by_key(&x, |&(point, _)| point) {
+            Ok(index) => self.moved[index].1,
+            Err(_) => x,
+        }
+    }
+
+    fn inv(&self) -> Self {
+        let mut moved: Vec<_> = self.moved.iter().map(|&(x, y)| (y, x)).collect();
+        moved.sort_unstable();
+        Self::from_sorted_unchecked(moved)
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        if self.is_id() {
+            return other.clone();
+        } else if other.is_id() {
+            return self.clone();
+        }
+
+        // Merge the two supports, as the product moves at most their union
+        let (lhs, rhs) = (&self.moved[..], &other.moved[..]);
+        let mut moved = Vec::with_capacity(lhs.len() + rhs.len());
+        let (mut i, mut j) = (0, 0);
+        while i < lhs.len() || j < rhs.len() {
+            let x = match (lhs.get(i), rhs.get(j)) {
+                (Some(&(a, _)), Some(&(b, _))) => a.min(b),
+                (Some(&(a, _)), None) => a,
+                (None, Some(&(b, _))) => b,
+                (None, None) => unreachable!(),
+            };
+
+            let mut image = x;
+            if i < lhs.len() && lhs[i].0 == x {
+                image = lhs[i].1;
+                i += 1;
+            }
+            if j < rhs.len() && rhs[j].0 == x {
+                j += 1;
+            }
+
+            let image = other.apply(image);
+            if image != x {
+                moved.push((x, image));
+            }
+        }
+        Self::from_sorted_unchecked(moved)
+    }
+
+    fn shift(&self, k: usize) -> Self {
+        Self::from_sorted_unchecked(self.moved.iter().map(|&(x, y)| (x + k, y + k)).collect())
+    }
+
+    fn lmp(&self) -> Option<usize> {
+        self.moved.last().map(|&(x, _)| x)
+    }
+
+    fn order(&self) -> usize {
+        self.cycle_lengths().into_iter().fold(1, lcm)
+    }
+
+    fn cycle_type(&self) -> Vec<usize> {
+        let mut lengths = self.cycle_lengths();
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        lengths
+    }
+
+    fn num_cycles(&self) -> usize {
+        self.cycles().count()
+    }
+
+    fn parity(&self) -> usize {
+        self.cycle_lengths().into_iter().map(|len| len - 1).sum::<usize>() % 2
+    }
+
+    fn support(&self) -> Vec<usize> {
+        self.moved.iter().map(|&(x, _)| x).collect()
+    }
+}
+
+// Written from the cycles, as going through CyclePermutation would need the images up to the largest moved point
+impl fmt::Display for SparsePermutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_id() {
+            return write!(f, "()");
+        }
+
+        for cycle in self.cycles() {
+            write!(f, "(")?;
+            for (i, x) in cycle.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", x + 1)?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<usize> for SparsePermutation {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        Self::from_images(&iter.into_iter().collect::<Vec<_>>()[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            perm::utils::ImageError,
+            Error,
+        },
+    };
+
+    const HUGE: usize = 1_000_000_000;
+
+    #[test]
+    fn from_pairs() {
+        let perm = SparsePermutation::from_pairs(vec![(HUGE, 3), (3, HUGE), (7, 7)]);
+        assert_eq!(perm.as_pairs(), &[(3, HUGE), (HUGE, 3)]);
+        assert_eq!(perm.apply(HUGE), 3);
+        assert_eq!(perm.apply(7), 7);
+        assert_eq!(perm.lmp(), Some(HUGE));
+    }
+
+    #[test]
+    fn invalid_pairs() {
+        assert_eq!(
+            SparsePermutation::try_from_pairs(vec![(1, 2), (1, 3)]),
+            Err(Error::RepeatedPoint(1))
+        );
+        assert_eq!(
+            SparsePermutation::try_from_pairs(vec![(1, 2), (2, 3)]),
+            Err(Error::InvalidImages(ImageError::MissingValue(1)))
+        );
+        assert_eq!(
+            SparsePermutation::try_from_pairs(vec![(2, 1), (3, 1)]),
+            Err(Error::InvalidImages(ImageError::DuplicatedImage(1)))
+        );
+    }
+
+    #[test]
+    fn huge_degree() {
+        // (HUGE, HUGE + 1, HUGE + 2) and (0 HUGE)
+        let a = SparsePermutation::from_pairs(vec![(HUGE, HUGE + 1), (HUGE + 1, HUGE + 2), (HUGE + 2, HUGE)]);
+        let b = SparsePermutation::from_pairs(vec![(0, HUGE), (HUGE, 0)]);
+        let ab = a.multiply(&b);
+        assert_eq!(ab.apply(HUGE + 2), 0);
+        assert_eq!(ab.apply(0), HUGE);
+        assert_eq!(ab.support(), vec![0, HUGE, HUGE + 1, HUGE + 2]);
+        assert_eq!(ab.cycle_type(), vec![4]);
+        assert_eq!(ab.order(), 4);
+        assert_eq!(ab.sign(), -1);
+        assert!(ab.multiply(&ab.inv()).is_id());
+        assert_eq!(a.shift(HUGE).apply(2 * HUGE + 2), 2 * HUGE);
+    }
+
+    #[test]
+    fn display() {
+        use crate::perm::impls::standard::StandardPermutation;
+
+        let perm = StandardPermutation::from_images(&[3, 0, 4, 1, 2, 5, 7, 6]);
+        assert_eq!(SparsePermutation::from(perm.clone()).to_string(), perm.to_string());
+        assert_eq!(SparsePermutation::id().to_string(), "()");
+        assert_eq!(
+            SparsePermutation::from_pairs(vec![(HUGE, 3), (3, HUGE)]).to_string(),
+            format!("(4 {})", HUGE + 1)
+        );
+    }
+
+    #[test]
+    fn cancellation() {
+        let a = SparsePermutation::from_pairs(vec![(5, 9), (9, 5)]);
+        assert!(a.multiply(&a).is_id());
+        assert_eq!(a.multiply(&a).as_pairs(), &[]);
+    }
+}
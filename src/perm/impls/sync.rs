@@ -24,6 +24,12 @@ impl SyncPermutation {
         Self::from_vec_unchecked(vals)
     }
 
+    /// Build from the images, or fail if they do not define a permutation
+    pub fn try_from_vec(vals: Vec<usize>) -> Result<Self, crate::Error> {
+        crate::perm::utils::valid_images(&vals[..])?;
+        Ok(Self::from_vec_unchecked(vals))
+    }
+
     pub(crate) fn from_vec_unchecked(mut vals: Vec<usize>) -> Self {
         while !vals.is_empty() && vals[vals.len() - 1] == vals.len() - 1 {
             vals.pop();
@@ -84,6 +90,12 @@ impl Permutation for SyncPermutation {
             let v: Vec<usize> = if self_size <= other_size {
                 // We can skip bounds checking in this case, and ignore lhs for larger points.
                 let mut result = Vec::with_capacity(other_size + 1);
+                #[cfg(feature = "simd")]
+                // Safety: the images of self are at most self_size
+                unsafe {
+                    crate::perm::simd::gather(&other.vals, &self.vals, &mut result)
+                };
+                #[cfg(not(feature = "simd"))]
                 for i in 0..(self_size + 1) {
                     result.push(other.vals[self.vals[i]]);
                 }
@@ -117,6 +129,30 @@ impl Permutation for SyncPermutation {
         images.extend(new_images);
         Self::from_vec_unchecked(images)
     }
+
+    fn order(&self) -> usize {
+        crate::perm::algos::order(&self.vals[..])
+    }
+
+    fn cycle_type(&self) -> Vec<usize> {
+        crate::perm::algos::cycle_type(&self.vals[..])
+    }
+
+    fn num_cycles(&self) -> usize {
+        crate::perm::algos::cycle_lengths(&self.vals[..]).len()
+    }
+
+    fn parity(&self) -> usize {
+        crate::perm::algos::parity(&self.vals[..])
+    }
+
+    fn support(&self) -> Vec<usize> {
+        crate::perm::algos::support(&self.vals[..])
+    }
+
+    fn fixed_points(&self) -> Vec<usize> {
+        crate::perm::algos::fixed_points(&self.vals[..])
+    }
 }
 
 impl PartialEq for SyncPermutation {
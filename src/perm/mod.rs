@@ -7,8 +7,13 @@ pub mod algos;
 pub mod builder;
 pub mod export;
 pub mod impls;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod slp;
 pub mod utils;
 
+pub use builder::LazyPerm;
+
 use std::{
     fmt::{
         Debug,
@@ -17,9 +22,6 @@ use std::{
     hash::Hash,
 };
 
-// Very much arbitrary
-const ORDER_LIMIT: usize = 12;
-
 /// The DefaultPermutation type. It is the permutation that, trough our testing,
 /// seems to perform better
 pub type DefaultPermutation = impls::standard::StandardPermutation;
@@ -29,6 +31,12 @@ pub trait Permutation: Clone + Eq + Hash + Debug + Display {
     /// Given some images, build a permutation
     fn from_images(images: &[usize]) -> Self;
 
+    /// Given some images, build a permutation, or fail if they do not define one
+    fn try_from_images(images: &[usize]) -> Result<Self, crate::Error> {
+        utils::valid_images(images)?;
+        Ok(Self::from_images(images))
+    }
+
     /// Get the images of the permutation
     fn images(&self) -> Vec<usize> {
         self.lmp()
@@ -63,11 +71,42 @@ pub trait Permutation: Clone + Eq + Hash + Debug + Display {
 
     /// Get the order of the permutation
     fn order(&self) -> usize {
-        match self.lmp() {
-            Some(n) if n <= ORDER_LIMIT => algos::order_mult(self),
-            Some(_) => algos::order_cycle(self),
-            None => 1,
-        }
+        algos::order(&self.images()[..])
+    }
+
+    /// Iterate over the non-trivial cycles, each starting from its smallest point, by increasing smallest point
+    fn cycles(&self) -> algos::Cycles<'_, Self> {
+        algos::Cycles::new(self)
+    }
+
+    /// The lengths of the non-trivial cycles, in non-increasing order
+    fn cycle_type(&self) -> Vec<usize> {
+        algos::cycle_type(&self.images()[..])
+    }
+
+    /// The number of non-trivial cycles
+    fn num_cycles(&self) -> usize {
+        algos::cycle_lengths(&self.images()[..]).len()
+    }
+
+    /// 0 if the permutation is even, 1 if it is odd
+    fn parity(&self) -> usize {
+        algos::parity(&self.images()[..])
+    }
+
+    /// 1 if the permutation is even, -1 if it is odd
+    fn sign(&self) -> isize {
+        1 - 2 * self.parity() as isize
+    }
+
+    /// The points moved by the permutation, in increasing order
+    fn support(&self) -> Vec<usize> {
+        algos::support(&self.images()[..])
+    }
+
+    /// The points smaller than the largest moved point that are fixed, in increasing order
+    fn fixed_points(&self) -> Vec<usize> {
+        algos::fixed_points(&self.images()[..])
     }
 
     /// Computes self * other^-1
@@ -87,12 +126,13 @@ pub trait Action<P>: Default + Clone + Debug {
     /// (2) action.apply(a b, i) == action.apply(b, action.apply(a, i))
     fn apply(&self, p: &P, input: Self::OrbitT) -> Self::OrbitT;
 
-    /// Same as apply, but can work for permutation words.
-    fn apply_word(&self, p: &WordPermutation<P>, input: Self::OrbitT) -> Self::OrbitT
+    /// Same as apply, but can work for lazy products.
+    /// By default the letters of the word are applied one by one, so that the word is never evaluated.
+    fn apply_word(&self, p: &LazyPerm<P>, input: Self::OrbitT) -> Self::OrbitT
     where
         P: Permutation,
     {
-        self.apply(&p.evaluate(), input)
+        p.iter().fold(input, |x, letter| self.apply(letter, x))
     }
 }
 
@@ -133,12 +173,23 @@ macro_rules! impl_all {
 use crate::perm::impls::{
     based::BasedPermutation,
     map::MapPermutation,
+    small::SmallPermutation,
+    sparse::SparsePermutation,
     standard::StandardPermutation,
     sync::SyncPermutation,
     word::WordPermutation,
 };
 
-impl_all!([StandardPermutation], BasedPermutation, MapPermutation, WordPermutation,);
+impl_all!(
+    [StandardPermutation],
+    BasedPermutation,
+    MapPermutation,
+    WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation,
+);
 
 impl_all!(
     [BasedPermutation],
@@ -146,6 +197,10 @@ impl_all!(
     MapPermutation,
     SyncPermutation,
     WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation,
 );
 
 impl_all!(
@@ -154,9 +209,22 @@ impl_all!(
     StandardPermutation,
     SyncPermutation,
     WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation,
 );
 
-impl_all!([SyncPermutation], MapPermutation, BasedPermutation, WordPermutation,);
+impl_all!(
+    [SyncPermutation],
+    MapPermutation,
+    BasedPermutation,
+    WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation,
+);
 
 impl_all_conversions!(
     [WordPermutation],
@@ -164,4 +232,50 @@ impl_all_conversions!(
     MapPermutation,
     BasedPermutation,
     StandardPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
+    SparsePermutation,
+);
+
+impl_all_conversions!(
+    [SmallPermutation<u8>],
+    StandardPermutation,
+    SyncPermutation,
+    BasedPermutation,
+    MapPermutation,
+    WordPermutation,
+    SparsePermutation,
+);
+
+impl_all_conversions!(
+    [SmallPermutation<u16>],
+    StandardPermutation,
+    SyncPermutation,
+    BasedPermutation,
+    MapPermutation,
+    WordPermutation,
+    SparsePermutation,
+);
+
+impl_all_conversions!(
+    [SmallPermutation<u32>],
+    StandardPermutation,
+    SyncPermutation,
+    BasedPermutation,
+    MapPermutation,
+    WordPermutation,
+    SparsePermutation,
+);
+
+impl_all_conversions!(
+    [SparsePermutation],
+    StandardPermutation,
+    SyncPermutation,
+    BasedPermutation,
+    MapPermutation,
+    WordPermutation,
+    SmallPermutation<u8>,
+    SmallPermutation<u16>,
+    SmallPermutation<u32>,
 );
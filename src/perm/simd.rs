@@ -0,0 +1,270 @@
+//! Vectorized kernels for the multiplication and inversion of permutations, enabled by the `simd` feature.
+//!
+//! Multiplying two permutations is a gather on the images, and inverting one is a scatter. The kernels use AVX2
+//! gathers, AVX-512 scatters and, for permutations on at most 64 points stored as bytes, SSSE3 byte shuffles.
+//! The instruction sets are detected at runtime, and the portable scalar loops are used when they are missing.
+//!
+//! All the kernels are unsafe, as the vector instructions do not check bounds: the caller has to make sure that
+//! every index is in bounds of the table, and that inverted images define a permutation.
+
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+use std::arch::x86_64::*;
+
+/// Push `table[i]` to `out`, for every `i` in `indices`.
+///
+/// # Safety
+/// Every index has to be smaller than `table.len()`.
+pub unsafe fn gather(table: &[usize], indices: &[usize], out: &mut Vec<usize>) {
+    debug_assert!(indices.iter().all(|&i| i < table.len()));
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return gather_avx2(table, indices, out);
+        }
+    }
+    out.extend(indices.iter().map(|&i| *table.get_unchecked(i)));
+}
+
+/// Same as [gather], for images stored as `u32`.
+///
+/// # Safety
+/// Every index has to be smaller than `table.len()`.
+pub unsafe fn gather_u32(table: &[u32], indices: &[u32], out: &mut Vec<u32>) {
+    debug_assert!(indices.iter().all(|&i| (i as usize) < table.len()));
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    {
+        // The gather takes signed offsets
+        if table.len() <= i32::MAX as usize && is_x86_feature_detected!("avx2") {
+            return gather_u32_avx2(table, indices, out);
+        }
+    }
+    out.extend(indices.iter().map(|&i| *table.get_unchecked(i as usize)));
+}
+
+/// Same as [gather], for images stored as `u8`.
+///
+/// # Safety
+/// Every index has to be smaller than `table.len()`.
+pub unsafe fn gather_u8(table: &[u8], indices: &[u8], out: &mut Vec<u8>) {
+    debug_assert!(indices.iter().all(|&i| (i as usize) < table.len()));
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    {
+        if table.len() <= 64 && is_x86_feature_detected!("ssse3") {
+            return gather_u8_ssse3(table, indices, out);
+        }
+    }
+    out.extend(indices.iter().map(|&i| *table.get_unchecked(i as usize)));
+}
+
+/// The inverse of the images.
+///
+/// # Safety
+/// The images have to define a permutation on `0..vals.len()`.
+pub unsafe fn invert(vals: &[usize]) -> Vec<usize> {
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    {
+        if is_x86_feature_detected!("avx512f") {
+            return invert_avx512(vals);
+        }
+    }
+    let mut v = vec![0; vals.len()];
+    for (i, &val) in vals.iter().enumerate() {
+        *v.get_unchecked_mut(val) = i;
+    }
+    v
+}
+
+/// Same as [invert], for images stored as `u32`.
+///
+/// # Safety
+/// The images have to define a permutation on `0..vals.len()`.
+pub unsafe fn invert_u32(vals: &[u32]) -> Vec<u32> {
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    {
+        if vals.len() <= i32::MAX as usize && is_x86_feature_detected!("avx512f") {
+            return invert_u32_avx512(vals);
+        }
+    }
+    let mut v = vec![0; vals.len()];
+    for (i, &val) in vals.iter().enumerate() {
+        *v.get_unchecked_mut(val as usize) = i as u32;
+    }
+    v
+}
+
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn gather_avx2(table: &[usize], indices: &[usize], out: &mut Vec<usize>) {
+    out.reserve(indices.len());
+    let start = out.len();
+    let dst = out.as_mut_ptr().add(start);
+    let chunks = indices.len() / 4;
+    for c in 0..chunks {
+        let idx = _mm256_loadu_si256(indices.as_ptr().add(4 * c) as *const __m256i);
+        let images = _mm256_i64gather_epi64::<8>(table.as_ptr() as *const i64, idx);
+        _mm256_storeu_si256(dst.add(4 * c) as *mut __m256i, images);
+    }
+    for i in 4 * chunks..indices.len() {
+        *dst.add(i) = *table.get_unchecked(*indices.get_unchecked(i));
+    }
+    out.set_len(start + indices.len());
+}
+
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn gather_u32_avx2(table: &[u32], indices: &[u32], out: &mut Vec<u32>) {
+    out.reserve(indices.len());
+    let start = out.len();
+    let dst = out.as_mut_ptr().add(start);
+    let chunks = indices.len() / 8;
+    for c in 0..chunks {
+        let idx = _mm256_loadu_si256(indices.as_ptr().add(8 * c) as *const __m256i);
+        let images = _mm256_i32gather_epi32::<4>(table.as_ptr() as *const i32, idx);
+        _mm256_storeu_si256(dst.add(8 * c) as *mut __m256i, images);
+    }
+    for i in 8 * chunks..indices.len() {
+        *dst.add(i) = *table.get_unchecked(*indices.get_unchecked(i) as usize);
+    }
+    out.set_len(start + indices.len());
+}
+
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn gather_u8_ssse3(table: &[u8], indices: &[u8], out: &mut Vec<u8>) {
+    // The table is split in blocks of 16 bytes, and every block is shuffled by the indices that fall into it
+    let mut padded = [0u8; 64];
+    padded[..table.len()].copy_from_slice(table);
+    let blocks = table.len().div_ceil(16);
+    let mut shuffles = [_mm_setzero_si128(); 4];
+    for (k, shuffle) in shuffles.iter_mut().enumerate().take(blocks) {
+        *shuffle = _mm_loadu_si128(padded.as_ptr().add(16 * k) as *const __m128i);
+    }
+
+    out.reserve(indices.len());
+    let start = out.len();
+    let dst = out.as_mut_ptr().add(start);
+    let chunks = indices.len() / 16;
+    for c in 0..chunks {
+        let idx = _mm_loadu_si128(indices.as_ptr().add(16 * c) as *const __m128i);
+        let mut images = _mm_setzero_si128();
+        for (k, shuffle) in shuffles.iter().enumerate().take(blocks) {
+            // Indices below the block become negative, and the ones above it get the high bit set by the
+            // comparison, so that the shuffle zeroes both
+            let shifted = _mm_sub_epi8(idx, _mm_set1_epi8(16 * k as i8));
+            let above = _mm_cmpgt_epi8(shifted, _mm_set1_epi8(15));
+            images = _mm_or_si128(images, _mm_shuffle_epi8(*shuffle, _mm_or_si128(shifted, above)));
+        }
+        _mm_storeu_si128(dst.add(16 * c) as *mut __m128i, images);
+    }
+    for i in 16 * chunks..indices.len() {
+        *dst.add(i) = *table.get_unchecked(*indices.get_unchecked(i) as usize);
+    }
+    out.set_len(start + indices.len());
+}
+
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+#[target_feature(enable = "avx512f")]
+unsafe fn invert_avx512(vals: &[usize]) -> Vec<usize> {
+    let mut v = vec![0; vals.len()];
+    let chunks = vals.len() / 8;
+    let step = _mm512_set1_epi64(8);
+    let mut points = _mm512_set_epi64(7, 6, 5, 4, 3, 2, 1, 0);
+    for c in 0..chunks {
+        let idx = _mm512_loadu_si512(vals.as_ptr().add(8 * c) as *const _);
+        _mm512_i64scatter_epi64::<8>(v.as_mut_ptr() as *mut i64, idx, points);
+        points = _mm512_add_epi64(points, step);
+    }
+    for i in 8 * chunks..vals.len() {
+        *v.get_unchecked_mut(*vals.get_unchecked(i)) = i;
+    }
+    v
+}
+
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+#[target_feature(enable = "avx512f")]
+unsafe fn invert_u32_avx512(vals: &[u32]) -> Vec<u32> {
+    let mut v = vec![0; vals.len()];
+    let chunks = vals.len() / 16;
+    let step = _mm512_set1_epi32(16);
+    let mut points = _mm512_set_epi32(15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0);
+    for c in 0..chunks {
+        let idx = _mm512_loadu_si512(vals.as_ptr().add(16 * c) as *const _);
+        _mm512_i32scatter_epi32::<4>(v.as_mut_ptr() as *mut i32, idx, points);
+        points = _mm512_add_epi32(points, step);
+    }
+    for i in 16 * chunks..vals.len() {
+        *v.get_unchecked_mut(*vals.get_unchecked(i) as usize) = i as u32;
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        rand::seq::SliceRandom,
+    };
+
+    fn random_images(n: usize) -> Vec<usize> {
+        let mut images: Vec<_> = (0..n).collect();
+        images.shuffle(&mut rand::thread_rng());
+        images
+    }
+
+    fn scalar_inverse(images: &[usize]) -> Vec<usize> {
+        let mut inverse = vec![0; images.len()];
+        for (i, &image) in images.iter().enumerate() {
+            inverse[image] = i;
+        }
+        inverse
+    }
+
+    // Sizes around the number of lanes of every kernel
+    const SIZES: [usize; 12] = [0, 1, 3, 4, 7, 8, 15, 16, 17, 33, 64, 1000];
+
+    #[test]
+    fn gather_matches_scalar() {
+        for &n in SIZES.iter() {
+            let table = random_images(n + 5);
+            let indices = random_images(n);
+            let expected: Vec<_> = indices.iter().map(|&i| table[i]).collect();
+
+            let mut out = vec![42];
+            unsafe { gather(&table, &indices, &mut out) };
+            assert_eq!(out[1..], expected[..]);
+
+            let narrow = |v: &[usize]| v.iter().map(|&i| i as u32).collect::<Vec<_>>();
+            let mut out = Vec::new();
+            unsafe { gather_u32(&narrow(&table), &narrow(&indices), &mut out) };
+            assert_eq!(out, narrow(&expected));
+        }
+    }
+
+    #[test]
+    fn gather_u8_matches_scalar() {
+        for &n in SIZES.iter().filter(|&&n| n < 250) {
+            // Tables on both sides of the limit of the shuffles
+            for &extra in [0, 5, 70].iter() {
+                let table: Vec<_> = random_images(n + extra).into_iter().map(|i| i as u8).collect();
+                let indices: Vec<_> = random_images(n).into_iter().map(|i| i as u8).collect();
+                let expected: Vec<_> = indices.iter().map(|&i| table[i as usize]).collect();
+                let mut out = Vec::new();
+                unsafe { gather_u8(&table, &indices, &mut out) };
+                assert_eq!(out, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn invert_matches_scalar() {
+        for &n in SIZES.iter() {
+            let images = random_images(n);
+            let expected = scalar_inverse(&images);
+            assert_eq!(unsafe { invert(&images) }, expected);
+
+            let narrow: Vec<_> = images.iter().map(|&i| i as u32).collect();
+            let expected: Vec<_> = expected.into_iter().map(|i| i as u32).collect();
+            assert_eq!(unsafe { invert_u32(&narrow) }, expected);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Straight-line programs over a list of generators.
+//!
+//! A straight-line program (SLP) is a sequence of instructions, each of which either references
+//! a generator, or combines the result of previous instructions. Evaluating the last instruction
+//! on a concrete list of generators yields the element the program represents.
+
+use {
+    crate::perm::Permutation,
+    serde::{
+        Deserialize,
+        Serialize,
+    },
+};
+
+use std::fmt;
+
+/// A single instruction of a straight-line program
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SlpLine {
+    /// The i-th generator
+    Generator(usize),
+    /// The product of the results of two earlier lines
+    Product(usize, usize),
+    /// The inverse of the result of an earlier line
+    Inverse(usize),
+}
+
+/// A straight-line program. The element it represents is the result of the last line,
+/// and the empty program represents the identity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Slp {
+    lines: Vec<SlpLine>,
+}
+
+impl Slp {
+    /// The program that evaluates to the identity
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    /// The program that evaluates to the i-th generator
+    pub fn generator(i: usize) -> Self {
+        Self {
+            lines: vec![SlpLine::Generator(i)],
+        }
+    }
+
+    /// Build a program from its lines. Panics if a line references a line that is not before it.
+    pub fn from_lines(lines: Vec<SlpLine>) -> Self {
+        for (i, line) in lines.iter().enumerate() {
+            match *line {
+                SlpLine::Generator(_) => {}
+                SlpLine::Product(a, b) => assert!(a < i && b < i, "Line {} references a later line", i),
+                SlpLine::Inverse(a) => assert!(a < i, "Line {} references a later line", i),
+            }
+        }
+
+        Self { lines }
+    }
+
+    /// Get the lines of the program
+    pub fn lines(&self) -> &[SlpLine] {
+        &self.lines[..]
+    }
+
+    /// Number of lines in the program
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Is this the empty program (i.e. the identity)
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// The number of generators the program needs to be evaluated
+    pub fn required_generators(&self) -> usize {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                SlpLine::Generator(i) => Some(i + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Evaluate the program on the given generators.
+    /// Panics if the program references a generator that is not given.
+    pub fn evaluate<P: Permutation>(&self, gens: &[P]) -> P {
+        let mut results: Vec<P> = Vec::with_capacity(self.lines.len());
+        for line in &self.lines {
+            let res = match *line {
+                SlpLine::Generator(i) => gens[i].clone(),
+                SlpLine::Product(a, b) => results[a].multiply(&results[b]),
+                SlpLine::Inverse(a) => results[a].inv(),
+            };
+            results.push(res);
+        }
+
+        results.pop().unwrap_or_else(P::id)
+    }
+
+    /// Expand the program into a word in the generators, as a list of (generator, exponent) pairs
+    /// with exponent either 1 or -1. Note that the word can be exponentially longer than the program.
+    pub fn word(&self) -> Vec<(usize, isize)> {
+        let mut words: Vec<Vec<(usize, isize)>> = Vec::with_capacity(self.lines.len());
+        for line in &self.lines {
+            let word = match *line {
+                SlpLine::Generator(i) => vec![(i, 1)],
+                SlpLine::Product(a, b) => words[a].iter().chain(words[b].iter()).copied().collect(),
+                SlpLine::Inverse(a) => words[a].iter().rev().map(|&(i, e)| (i, -e)).collect(),
+            };
+            words.push(word);
+        }
+
+        words.pop().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Slp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.lines.is_empty() {
+            return write!(f, "[Slp: id]");
+        }
+
+        write!(f, "[Slp: ")?;
+        for (i, line) in self.lines.iter().enumerate() {
+            match line {
+                SlpLine::Generator(g) => write!(f, "r{} := g{}; ", i, g)?,
+                SlpLine::Product(a, b) => write!(f, "r{} := r{} * r{}; ", i, a, b)?,
+                SlpLine::Inverse(a) => write!(f, "r{} := r{}^-1; ", i, a)?,
+            }
+        }
+        write!(f, "return r{}]", self.lines.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::perm::{
+            export::CyclePermutation,
+            DefaultPermutation,
+        },
+    };
+
+    fn gens() -> Vec<DefaultPermutation> {
+        vec![
+            CyclePermutation::single_cycle(&[1, 2]).into(),
+            CyclePermutation::single_cycle(&[1, 2, 3, 4]).into(),
+        ]
+    }
+
+    #[test]
+    fn identity_evaluation() {
+        let slp = Slp::identity();
+        assert!(slp.is_empty());
+        assert!(slp.evaluate(&gens()).is_id());
+        assert!(slp.word().is_empty());
+    }
+
+    #[test]
+    fn program_evaluation() {
+        let gens = gens();
+        // (g1 * g0)^-1 * g1
+        let slp = Slp::from_lines(vec![
+            SlpLine::Generator(0),
+            SlpLine::Generator(1),
+            SlpLine::Product(1, 0),
+            SlpLine::Inverse(2),
+            SlpLine::Product(3, 1),
+        ]);
+        let expected = gens[1].multiply(&gens[0]).inv().multiply(&gens[1]);
+        assert_eq!(slp.evaluate(&gens), expected);
+        assert_eq!(slp.required_generators(), 2);
+        assert_eq!(slp.word(), vec![(0, -1), (1, -1), (1, 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_reference() {
+        Slp::from_lines(vec![SlpLine::Generator(0), SlpLine::Product(0, 2)]);
+    }
+}
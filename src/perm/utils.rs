@@ -4,6 +4,7 @@ use {
         Permutation,
     },
     rand::seq::SliceRandom,
+    std::fmt,
 };
 
 use crate::perm::impls::standard::StandardPermutation;
@@ -31,12 +32,24 @@ pub fn order_n_permutation<P: Permutation>(start: usize, n: usize) -> P {
     P::from_images(images)
 }
 
-#[derive(Debug)]
+/// Why a list of images does not define a permutation
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ImageError {
     DuplicatedImage(usize),
     MissingValue(usize),
 }
 
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicatedImage(x) => write!(f, "{} is the image of more than one point", x),
+            Self::MissingValue(x) => write!(f, "{} is not the image of any point", x),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
 /// Check that an array is in the right format
 pub fn valid_images(images: &[usize]) -> Result<(), ImageError> {
     use std::cmp::Ordering;
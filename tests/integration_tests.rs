@@ -56,7 +56,7 @@ fn load_libraries(zip: &str) -> Vec<DecoratedGroup<SyncPermutation>> {
 
     for i in 0..archive.len() {
         let mut file = archive.by_index(i).unwrap();
-        let outpath = file.sanitized_name();
+        let outpath = file.mangled_name();
 
         if let Some(p) = outpath.parent() {
             if !p.exists() {